@@ -0,0 +1,102 @@
+use std::{hint::black_box, time::Duration};
+
+use actix_web::{dev::ServiceResponse, http::header::HeaderValue, test};
+use criterion::{criterion_group, criterion_main, Criterion};
+use tokio::runtime;
+
+mod common;
+use common::{prep_service, ETAG_REFW, SECS_PER_BENCH};
+
+// The header parsers are private to the crate, so they are included directly
+// to compare the old path with the new one.
+#[allow(dead_code)]
+#[path = "../src/parse.rs"]
+mod parse;
+use parse::if_none_match_matches;
+
+/// How `If-None-Match` was parsed before single ETags were compared directly:
+/// every entry went through a regex and was collected into a list.
+mod baseline {
+    use actix_web::http::header::HeaderValue;
+    use lazy_static::lazy_static;
+    use regex::Regex;
+
+    pub fn parse_if_none_match_value(value: &HeaderValue) -> Option<Vec<&str>> {
+        value.to_str().ok().map(|v| {
+            v.split(',')
+                .filter_map(parse_single_etag_value)
+                .collect::<Vec<&str>>()
+        })
+    }
+
+    fn parse_single_etag_value(value: &str) -> Option<&str> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r#"^ *(W/)?(?P<value>"[^"]+") *$"#).unwrap();
+        }
+
+        RE.captures(value)
+            .and_then(|v| v.name("value"))
+            .map(|v| v.as_str())
+    }
+}
+
+async fn test_refw(
+    app: impl actix_web::dev::Service<
+        actix_http::Request,
+        Response = ServiceResponse,
+        Error = actix_web::Error,
+    >,
+    if_none_match: &str,
+) {
+    let req = test::TestRequest::get()
+        .uri("/refw/")
+        .append_header(("If-None-Match", if_none_match))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 304);
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("If-None-Match comparison");
+    group.measurement_time(Duration::from_secs(SECS_PER_BENCH));
+
+    let runtime = runtime::Builder::new_current_thread().build().unwrap();
+    let app = prep_service(&runtime);
+
+    // A single ETag is compared directly, while a list has to be parsed in
+    // full. The list ends with the matching ETag so both get a 304.
+    let list = format!(r#""stale-1", W/"stale-2", {ETAG_REFW}"#);
+
+    group.bench_with_input("single etag", &app, |b, app| {
+        b.to_async(&runtime).iter(|| test_refw(app, ETAG_REFW))
+    });
+
+    group.bench_with_input("list of etags", &app, |b, app| {
+        b.to_async(&runtime).iter(|| test_refw(app, &list))
+    });
+
+    group.finish();
+
+    let mut group = c.benchmark_group("If-None-Match single etag parsing");
+    group.measurement_time(Duration::from_secs(SECS_PER_BENCH));
+
+    let value = HeaderValue::from_static(ETAG_REFW);
+    let etag = ETAG_REFW;
+    let baseline =
+        |value| baseline::parse_if_none_match_value(value).map(|etags| etags.contains(&etag));
+    assert_eq!(baseline(&value), Some(true));
+    assert_eq!(if_none_match_matches(&value, etag), Some(true));
+
+    group.bench_function("baseline regex parsing", |b| {
+        b.iter(|| baseline(black_box(&value)))
+    });
+
+    group.bench_function("compare directly", |b| {
+        b.iter(|| if_none_match_matches(black_box(&value), etag))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -10,7 +10,9 @@ mod common;
 use common::{prep_service, ETAG_RE, ETAG_REFW, SECS_PER_BENCH};
 
 lazy_static! {
-    static ref NOW: String = chrono::Utc::now().to_rfc2822();
+    static ref NOW: String = chrono::Utc::now()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
 }
 
 async fn test_re(
@@ -45,9 +47,9 @@ async fn test_re(
         .to_request();
     let resp = test::call_and_read_body(&app, req).await;
     assert!(!resp.is_empty());
-    // Make a cached request again, but this time use "If-Unmodified-Since"
+    // Make a cached request again, but this time use "If-Modified-Since"
     let req = test::TestRequest::get()
-        .append_header(("If-Unmodified-Since", NOW.as_str()))
+        .append_header(("If-Modified-Since", NOW.as_str()))
         .uri(path)
         .to_request();
     let resp = test::call_service(&app, req).await;
@@ -99,9 +101,9 @@ async fn test_refw(
         .to_request();
     let resp = test::call_and_read_body(&app, req).await;
     assert!(!resp.is_empty());
-    // Make a cached request again, but this time use "If-Unmodified-Since"
+    // Make a cached request again, but this time use "If-Modified-Since"
     let req = test::TestRequest::get()
-        .append_header(("If-Unmodified-Since", NOW.as_str()))
+        .append_header(("If-Modified-Since", NOW.as_str()))
         .uri(path)
         .to_request();
     let resp = test::call_service(&app, req).await;
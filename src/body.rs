@@ -0,0 +1,77 @@
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_web::{
+    body::{BodySize, MessageBody},
+    web::Bytes,
+};
+
+/// The body of a response to a `HEAD` request.
+///
+/// It reports the size that the body of the matching `GET` response would
+/// have, so that the `Content-Length` header is correct, but it has no data.
+pub(crate) struct HeadBody(pub(crate) u64);
+
+impl MessageBody for HeadBody {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Sized(self.0)
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        Poll::Ready(None)
+    }
+}
+
+/// How much of the file is sent at a time when a file is streamed.
+pub(crate) const STREAMING_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A body that sends the data in chunks instead of all at once.
+///
+/// The size is still known up front, so the response has a `Content-Length`
+/// rather than using chunked transfer encoding. Each chunk is a slice of the
+/// same `Bytes`, so the data is only copied if it can't be turned into `Bytes`
+/// without copying, which is never the case for the embeds or memory files.
+pub(crate) struct StreamingBody {
+    data: Bytes,
+    offset: usize,
+}
+
+impl StreamingBody {
+    pub(crate) fn new<B: MessageBody + AsRef<[u8]>>(data: B) -> Self {
+        let data = match data.try_into_bytes() {
+            Ok(data) => data,
+            Err(data) => Bytes::copy_from_slice(data.as_ref()),
+        };
+        StreamingBody { data, offset: 0 }
+    }
+}
+
+impl MessageBody for StreamingBody {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Sized(self.data.len() as u64)
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.get_mut();
+        if this.offset >= this.data.len() {
+            return Poll::Ready(None);
+        }
+        let end = usize::min(this.offset + STREAMING_CHUNK_SIZE, this.data.len());
+        let chunk = this.data.slice(this.offset..end);
+        this.offset = end;
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
@@ -0,0 +1,36 @@
+/// Where the responder gets the current time from.
+///
+/// The current time is only used for headers that are relative to it, like
+/// `Expires`. The system clock is used unless a response is given another one
+/// with `EmbedResponse::with_clock`, which is mostly useful for tests.
+///
+/// ```ignore
+/// Embed::get("style.css")
+///     .into_response()
+///     .with_expires(Duration::from_secs(3600))
+///     .with_clock(FixedClock(1445412480))
+/// ```
+pub trait Clock {
+    /// The current time, as a UNIX timestamp.
+    fn now(&self) -> i64;
+}
+
+/// The clock of the system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+}
+
+/// A clock that is stopped at this UNIX timestamp.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub i64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.0
+    }
+}
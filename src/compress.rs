@@ -10,6 +10,13 @@ use lazy_static::lazy_static;
 use regex::Regex;
 
 /// When should the server try sending a compressed response?
+///
+/// This works fine together with the `Compress` middleware from `actix-web`.
+/// Compressed responses always carry a `Content-Encoding` header, which makes
+/// the middleware skip them instead of compressing them a second time. You can
+/// still use the middleware to compress the responses that this crate sends
+/// uncompressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Compress {
     /// Never compress responses, even if a precompressed response is available.
     Never,
@@ -21,6 +28,7 @@ pub enum Compress {
     /// This option will only work with `rust-embed-for-web` and only if compression has not been disabled.
     /// With `rust-embed`, or if the `rust-embed-for-web` resource is tagged with `#[gzip = "false"]` this is equivalent to Never.
     ///
+    #[default]
     IfPrecompressed,
     /// Perform on-the-fly compression if the file mime type is well known to be compressible.
     ///
@@ -37,10 +45,33 @@ pub enum Compress {
     Always,
 }
 
-impl Default for Compress {
-    fn default() -> Self {
-        Self::IfPrecompressed
-    }
+/// What to do when a client asks for a range of the file and would also accept
+/// it compressed.
+///
+/// Ranges are offsets into the uncompressed file, so a response can't be both
+/// a range and compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangeCompressionPolicy {
+    /// Serve the range uncompressed with `206 Partial Content`. A client asking
+    /// for a range most likely wants only that part of the file, for example
+    /// to resume a download or to seek in a video.
+    #[default]
+    PreferRange,
+    /// Ignore the range and serve the whole file compressed with `200 OK`, if
+    /// the file would be compressed at all. Files that are sent uncompressed
+    /// still get the range.
+    PreferCompression,
+}
+
+/// An encoding the file can be sent with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Uncompressed.
+    Identity,
+    /// Compressed with gzip.
+    Gzip,
+    /// Compressed with brotli.
+    Br,
 }
 
 /// This is basically a list of text mime types, plus javascript, json, and xml.
@@ -53,6 +84,57 @@ pub(crate) fn is_well_known_compressible_mime_type(mime_type: &str) -> bool {
     RE.is_match(mime_type)
 }
 
+/// Mime types that are never compressed unless configured otherwise. These
+/// formats are already compressed, so compressing them again only wastes CPU
+/// time and can make them slightly larger.
+pub(crate) const DEFAULT_COMPRESSION_EXCLUDED_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "image/avif",
+    "video/mp4",
+    "video/webm",
+    "audio/mpeg",
+    "audio/ogg",
+    "font/woff",
+    "font/woff2",
+    "application/zip",
+    "application/gzip",
+    "application/x-gzip",
+    "application/x-compressed",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/zstd",
+    "application/x-7z-compressed",
+];
+
+/// Extensions of compressed formats. Files with these extensions are sent as
+/// they are, since compressing them again would only waste CPU time, and a
+/// client would have to decompress them twice.
+const COMPRESSED_EXTENSIONS: &[&str] = &["gz", "tgz", "br", "zst", "bz2", "xz", "zip", "7z"];
+
+/// Whether the path has the extension of a compressed format, like
+/// `archive.tar.gz` or `app.js.br`.
+pub(crate) fn has_compressed_extension(path: &str) -> bool {
+    path.rsplit_once('.')
+        .map(|(_, extension)| {
+            COMPRESSED_EXTENSIONS
+                .iter()
+                .any(|compressed| compressed.eq_ignore_ascii_case(extension))
+        })
+        .unwrap_or(false)
+}
+
+/// Checks if the mime type is in the list, ignoring any parameters like the
+/// charset.
+pub(crate) fn is_excluded_mime_type<S: AsRef<str>>(mime_type: &str, excluded: &[S]) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or("").trim();
+    excluded
+        .iter()
+        .any(|excluded| excluded.as_ref().eq_ignore_ascii_case(essence))
+}
+
 // Putting the data into cache could potentially fail. That's okay if it does
 // happen, we have no way of handling that and we might as well just keep
 // serving files.
@@ -62,16 +144,18 @@ pub(crate) fn is_well_known_compressible_mime_type(mime_type: &str) -> bool {
 /// The compressed files are cached based on the hash values provided.
 /// Since we already have the hashes precomputed in rust-embed and rust-embed-for-web,
 /// we just reuse that instead of trying to hash the data this function gets.
-pub(crate) fn compress_data_gzip(hash: &str, data: &[u8]) -> Vec<u8> {
+/// Without a hash, the data is compressed every time and nothing is cached.
+pub(crate) fn compress_data_gzip(hash: Option<&str>, data: &[u8]) -> Vec<u8> {
     lazy_static! {
         static ref CACHED_GZIP_DATA: RwLock<HashMap<String, Vec<u8>>> = RwLock::new(HashMap::new());
     }
 
-    if let Some(data_gzip) = CACHED_GZIP_DATA
-        .read()
-        .ok()
-        .and_then(|cached| cached.get(hash).map(ToOwned::to_owned))
-    {
+    if let Some(data_gzip) = hash.and_then(|hash| {
+        CACHED_GZIP_DATA
+            .read()
+            .ok()
+            .and_then(|cached| cached.get(hash).map(ToOwned::to_owned))
+    }) {
         return data_gzip;
     }
 
@@ -79,9 +163,11 @@ pub(crate) fn compress_data_gzip(hash: &str, data: &[u8]) -> Vec<u8> {
     flate2::write::GzEncoder::new(&mut compressed, Compression::default())
         .write_all(data)
         .unwrap();
-    CACHED_GZIP_DATA
-        .write()
-        .map(|mut cached| cached.insert(hash.to_string(), compressed.clone()));
+    if let Some(hash) = hash {
+        CACHED_GZIP_DATA
+            .write()
+            .map(|mut cached| cached.insert(hash.to_string(), compressed.clone()));
+    }
     compressed
 }
 
@@ -94,16 +180,18 @@ pub(crate) fn compress_data_gzip(hash: &str, data: &[u8]) -> Vec<u8> {
 /// The compressed files are cached based on the hash values provided.
 /// Since we already have the hashes precomputed in rust-embed and rust-embed-for-web,
 /// we just reuse that instead of trying to hash the data this function gets.
-pub(crate) fn compress_data_br(hash: &str, data: &[u8]) -> Vec<u8> {
+/// Without a hash, the data is compressed every time and nothing is cached.
+pub(crate) fn compress_data_br(hash: Option<&str>, data: &[u8]) -> Vec<u8> {
     lazy_static! {
         static ref CACHED_BR_DATA: RwLock<HashMap<String, Vec<u8>>> = RwLock::new(HashMap::new());
     }
 
-    if let Some(data_gzip) = CACHED_BR_DATA
-        .read()
-        .ok()
-        .and_then(|cached| cached.get(hash).map(ToOwned::to_owned))
-    {
+    if let Some(data_gzip) = hash.and_then(|hash| {
+        CACHED_BR_DATA
+            .read()
+            .ok()
+            .and_then(|cached| cached.get(hash).map(ToOwned::to_owned))
+    }) {
         return data_gzip;
     }
 
@@ -115,9 +203,11 @@ pub(crate) fn compress_data_br(hash: &str, data: &[u8]) -> Vec<u8> {
         &BrotliEncoderParams::default(),
     )
     .expect("Failed to compress br data");
-    CACHED_BR_DATA
-        .write()
-        .map(|mut cached| cached.insert(hash.to_string(), compressed.clone()));
+    if let Some(hash) = hash {
+        CACHED_BR_DATA
+            .write()
+            .map(|mut cached| cached.insert(hash.to_string(), compressed.clone()));
+    }
     compressed
 }
 
@@ -140,45 +230,35 @@ mod test {
 
     #[test]
     fn javascript_file_is_compressible() {
-        assert_eq!(
-            is_well_known_compressible_mime_type("application/javascript"),
-            true
-        )
+        assert!(is_well_known_compressible_mime_type(
+            "application/javascript"
+        ))
     }
 
     #[test]
     fn json_file_is_compressible() {
-        assert_eq!(
-            is_well_known_compressible_mime_type("application/json"),
-            true
-        )
+        assert!(is_well_known_compressible_mime_type("application/json"))
     }
 
     #[test]
     fn xml_file_is_compressible() {
-        assert_eq!(
-            is_well_known_compressible_mime_type("application/xml"),
-            true
-        )
+        assert!(is_well_known_compressible_mime_type("application/xml"))
     }
 
     #[test]
     fn jpg_file_not_compressible() {
-        assert_eq!(is_well_known_compressible_mime_type("image/jpeg"), false)
+        assert!(!is_well_known_compressible_mime_type("image/jpeg"))
     }
 
     #[test]
     fn zip_file_not_compressible() {
-        assert_eq!(
-            is_well_known_compressible_mime_type("application/zip"),
-            false
-        )
+        assert!(!is_well_known_compressible_mime_type("application/zip"))
     }
 
     #[test]
     fn gzip_roundtrip() {
         let source = b"x123";
-        let compressed = compress_data_gzip("foo", source);
+        let compressed = compress_data_gzip(Some("foo"), source);
         let mut decompressed = Vec::new();
         flate2::write::GzDecoder::new(&mut decompressed)
             .write_all(&compressed)
@@ -191,10 +271,10 @@ mod test {
         let source = b"Et quos non sed magnam reiciendis praesentium quod libero. Architecto optio tempora iure aspernatur rerum voluptatem quas. Eos ut atque quas perspiciatis dolorem quidem. Cum et quo et. Voluptatum ut est id eligendi illum inventore. Est non rerum vel rem. Molestiae similique alias nihil harum qui. Consectetur et dolores autem. Magnam et saepe ad reprehenderit. Repellendus vel excepturi eaque esse error. Deserunt est impedit totam nostrum sunt. Eligendi magnam distinctio odit iste molestias est id. Deserunt odit similique magnam repudiandae aut saepe. Dolores laboriosam consectetur quos dolores ea. Non quod veniam quisquam molestias aut deserunt tempora. Mollitia consequuntur facilis doloremque provident eligendi similique possimus. Deleniti facere quam fugiat porro. Tenetur cupiditate eum consequatur beatae dolorum. Veniam voluptatem qui eum quasi corrupti. Quis necessitatibus maxime eum numquam ipsam ducimus expedita maiores. Aliquid voluptas non aut. Tempore dicta ut aperiam ipsum ut et esse explicabo.";
 
         let first_start = Instant::now();
-        compress_data_gzip("lorem", source);
+        compress_data_gzip(Some("lorem"), source);
         let first = first_start.elapsed();
         let second_start = Instant::now();
-        compress_data_gzip("lorem", source);
+        compress_data_gzip(Some("lorem"), source);
         let second = second_start.elapsed();
 
         // Check that the second call was faster
@@ -0,0 +1,75 @@
+use actix_web::{web, web::Bytes, HttpRequest};
+
+use crate::{compress::Compress, etag::EtagAlgorithm};
+
+/// Default settings for all the responses in an app.
+///
+/// Register this as app data, and every `EmbedResponse` will use these settings
+/// unless the response sets its own. Settings made on the response itself
+/// always override the defaults.
+///
+/// ```ignore
+/// App::new()
+///     .app_data(ResponderDefaults::new().with_cache_control("public, max-age=3600"))
+///     .service(serve_assets)
+/// ```
+///
+/// Both `.app_data(defaults)` and `.app_data(web::Data::new(defaults))` work.
+///
+/// Only these settings have app-wide defaults:
+///
+/// - `use_compression`
+/// - `with_cache_control`
+/// - `with_etag_algorithm`
+/// - `with_method_not_allowed_body`
+///
+/// Every other setting, like `with_expires`, `with_no_cache_for_errors` or
+/// `with_ranges`, has to be set on each response, and keeps its usual default
+/// otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct ResponderDefaults {
+    pub(crate) compress: Option<Compress>,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) etag_algorithm: Option<EtagAlgorithm>,
+    pub(crate) method_not_allowed_body: Option<(Bytes, String)>,
+}
+
+impl ResponderDefaults {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The default for `EmbedResponse::use_compression`.
+    pub fn use_compression(mut self, option: Compress) -> Self {
+        self.compress = Some(option);
+        self
+    }
+
+    /// The default for `EmbedResponse::with_cache_control`.
+    pub fn with_cache_control(mut self, value: impl Into<String>) -> Self {
+        self.cache_control = Some(value.into());
+        self
+    }
+
+    /// The default for `EmbedResponse::with_etag_algorithm`.
+    pub fn with_etag_algorithm(mut self, algorithm: EtagAlgorithm) -> Self {
+        self.etag_algorithm = Some(algorithm);
+        self
+    }
+
+    /// The default for `EmbedResponse::with_method_not_allowed_body`.
+    pub fn with_method_not_allowed_body(
+        mut self,
+        body: impl Into<Bytes>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        self.method_not_allowed_body = Some((body.into(), mime_type.into()));
+        self
+    }
+
+    /// Get the defaults registered for the app handling this request, if any.
+    pub fn from_app_data(req: &HttpRequest) -> Option<&Self> {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|v| v.get_ref()))
+    }
+}
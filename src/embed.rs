@@ -1,12 +1,38 @@
+use std::time::Duration;
+
 use actix_web::{
     body::{BoxBody, MessageBody},
-    http::Method,
-    HttpRequest, HttpResponse, Responder,
+    http::{
+        header::{HeaderName, HeaderValue, CACHE_CONTROL, CONTENT_TYPE, ETAG},
+        Method, StatusCode, Version,
+    },
+    web::Bytes,
+    HttpRequest, HttpResponse, HttpResponseBuilder, Responder,
 };
+use base64::{prelude::BASE64_STANDARD, Engine};
 
 use crate::{
-    compress::Compress, compress_data_br, compress_data_gzip, helper::accepts_encoding,
-    is_well_known_compressible_mime_type, parse::parse_if_none_match_value,
+    body::{HeadBody, StreamingBody},
+    clock::{Clock, SystemClock},
+    compress::{
+        has_compressed_extension, is_excluded_mime_type, Compress, Encoding,
+        RangeCompressionPolicy, DEFAULT_COMPRESSION_EXCLUDED_TYPES,
+    },
+    compress_data_br, compress_data_gzip,
+    defaults::ResponderDefaults,
+    etag::{compute_etag, encoding_etag, is_empty_etag, sha256_hash, EtagAlgorithm, EtagMode},
+    helper::{accepts_encoding, format_http_date},
+    is_well_known_compressible_mime_type,
+    link::LinkHeader,
+    mime::MimeRegistry,
+    not_found::NotFound,
+    parse::{
+        if_none_match_matches, is_if_none_match_wildcard, is_valid_if_none_match_value,
+        is_valid_range_value, parse_http_date, parse_range_value,
+    },
+    rewrite::{rewrite_file, Rewrite},
+    serve_info::ServeInfo,
+    sniff::conflicts_with_content_type,
 };
 
 /// A common trait used internally to create HTTP responses.
@@ -33,6 +59,19 @@ pub trait EmbedRespondable {
     ///
     /// `Some` if precompression has been done, `None` if the file was not precompressed.
     fn data_br(&self) -> Option<Self::DataBr>;
+    /// Whether the file has been precompressed with gzip, which lets
+    /// `Compress::IfPrecompressed` send it compressed.
+    ///
+    /// This is normally whether there is `data_gzip`. A changed copy of a
+    /// precompressed file, which can't use the precompressed data, can return
+    /// `true` here to be compressed on the fly instead.
+    fn is_precompressed_gzip(&self) -> bool {
+        self.data_gzip().is_some()
+    }
+    /// Like `is_precompressed_gzip`, but for brotli.
+    fn is_precompressed_br(&self) -> bool {
+        self.data_br().is_some()
+    }
     /// The UNIX timestamp of when the file was last modified.
     fn last_modified_timestamp(&self) -> Option<i64>;
     /// The rfc2822 encoded last modified date.
@@ -41,100 +80,933 @@ pub trait EmbedRespondable {
     fn etag(&self) -> Self::ETag;
     /// The mime type for the file, if one has been guessed.
     fn mime_type(&self) -> Option<Self::MimeType>;
+    /// The name of the embedded file, if it is known.
+    fn file_name(&self) -> Option<String> {
+        None
+    }
+    /// The sha256 hash of the file, if it has already been computed.
+    ///
+    /// This is used by the ETag algorithms that need the hash, so they don't
+    /// have to compute it again.
+    fn sha256_hash(&self) -> Option<[u8; 32]> {
+        None
+    }
+    /// Whether the compressed data, computed ETags and rewrites of the file
+    /// can be cached.
+    ///
+    /// The caches are never emptied, which is fine for the fixed set of files
+    /// in an embed. Files generated at runtime should return `false`, so that
+    /// every new version of them doesn't stay in memory for good.
+    fn cacheable(&self) -> bool {
+        true
+    }
 }
 
 /// An opaque wrapper around the embedded file.
 ///
-/// You don't manually create these objects, you should use `.into_response()`
-/// or `.into()` to convert an embedded file into an `EmbedResponse`.
+/// You don't usually create these objects manually, you should use
+/// `.into_response()` or `.into()` to convert an embedded file into an
+/// `EmbedResponse`.
+///
+/// Creating the response doesn't allocate: every setting starts out as `None`,
+/// `false` or an empty `Vec`, and the file is moved in as it is. Builder
+/// methods that take a string or a list allocate to store it, the others
+/// don't. The allocations for the headers and the body happen in
+/// `respond_to`, where compressed data and computed ETags are cached after the
+/// first response so that later responses only allocate the headers.
 pub struct EmbedResponse<T: EmbedRespondable> {
     pub(crate) file: Option<T>,
-    pub(crate) compress: Compress,
+    pub(crate) compress: Option<Compress>,
+    pub(crate) gzip_excluded_types: Option<Vec<String>>,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) etag_algorithm: Option<EtagAlgorithm>,
+    pub(crate) method_not_allowed_body: Option<(Bytes, String)>,
+    pub(crate) content_type_from_request_path: bool,
+    pub(crate) offload_header: Option<(String, OffloadPath)>,
+    pub(crate) skip_compression_on_loopback: bool,
+    pub(crate) uncompressed_length_header: Option<String>,
+    pub(crate) timing_allow_origin: Option<String>,
+    pub(crate) expires: Option<Duration>,
+    pub(crate) strict_conditionals: bool,
+    pub(crate) content_type: Option<String>,
+    pub(crate) last_modified_timestamp: Option<i64>,
+    pub(crate) status: Option<StatusCode>,
+    pub(crate) auto_streaming: Option<usize>,
+    pub(crate) repr_digest: bool,
+    pub(crate) attachment: Option<Option<String>>,
+    pub(crate) on_response: Option<OnResponse>,
+    pub(crate) early_hints: Vec<LinkHeader>,
+    pub(crate) base_path: Option<String>,
+    pub(crate) range_compression_policy: RangeCompressionPolicy,
+    pub(crate) content_type_guard: bool,
+    pub(crate) no_transform: bool,
+    pub(crate) http10_compat: bool,
+    pub(crate) require_compression: bool,
+    pub(crate) reformat_last_modified: bool,
+    pub(crate) etag_mode: EtagMode,
+    pub(crate) managed_headers: Option<Vec<String>>,
+    pub(crate) strip_bom: bool,
+    pub(crate) clock: Option<Box<dyn Clock + Send + Sync>>,
+    pub(crate) no_store_errors: bool,
+    pub(crate) vary: Vec<String>,
+    pub(crate) max_ranges: usize,
+    pub(crate) nel: Option<String>,
+    pub(crate) report_to: Option<String>,
+    pub(crate) force_gzip: bool,
+    pub(crate) redirect: Option<String>,
+    pub(crate) weak_etag_on_via: bool,
+    pub(crate) forced_encoding: Option<Encoding>,
+    pub(crate) ranges: bool,
 }
 
+/// The headers that this responder sets to describe and cache the file, which
+/// can be restricted with `EmbedResponse::with_managed_headers`.
+///
+/// The headers that describe the body itself, like `Content-Encoding`,
+/// `Content-Length` and `Content-Range`, are not in this list. Those are always
+/// set, since the response would be wrong without them. For the same reason,
+/// the `Content-Type` of a `multipart/byteranges` body is always set, and
+/// responses without the file, like a 405 or a 416, keep all their headers.
+pub const MANAGED_HEADERS: &[&str] = &[
+    "ETag",
+    "Last-Modified",
+    "Cache-Control",
+    "Expires",
+    "Content-Type",
+    "Content-Disposition",
+    "Accept-Ranges",
+    "Link",
+    "Timing-Allow-Origin",
+    "Repr-Digest",
+    "Vary",
+    "NEL",
+    "Report-To",
+];
+
+/// How many ranges a single request can ask for by default.
+const DEFAULT_MAX_RANGES: usize = 10;
+
+/// Builds the value of the offload header from the request.
+pub(crate) type OffloadPath = Box<dyn Fn(&HttpRequest) -> String + Send + Sync>;
+
+/// Called with the information about the response once it's created.
+pub(crate) type OnResponse = Box<dyn FnOnce(&ServeInfo) + Send>;
+
 enum ShouldCompress {
     Gzip,
     Brotli,
     No,
 }
 
+impl ShouldCompress {
+    /// The name of the encoding, or `None` if the file is sent uncompressed.
+    fn encoding(&self) -> Option<&'static str> {
+        match self {
+            ShouldCompress::Gzip => Some("gzip"),
+            ShouldCompress::Brotli => Some("br"),
+            ShouldCompress::No => None,
+        }
+    }
+}
+
 fn should_compress<T: EmbedRespondable>(
     req: &HttpRequest,
     file: &T,
+    mime_type: Option<&str>,
     compress: &Compress,
+    excluded_types: Option<&[String]>,
 ) -> ShouldCompress {
-    let should_compress_for_encoding =
-        |is_precompressed_for_encoding: bool, mime_type: Option<T::MimeType>, encoding: &str| {
-            accepts_encoding(req, encoding)
-                && match compress {
-                    Compress::Never => false,
-                    Compress::IfPrecompressed => is_precompressed_for_encoding,
-                    Compress::IfWellKnown => mime_type
-                        .map(|v| is_well_known_compressible_mime_type(v.as_ref()))
-                        .unwrap_or(false),
-                    Compress::Always => true,
-                }
-        };
+    if is_excluded_from_compression(req, file, mime_type, excluded_types) {
+        return ShouldCompress::No;
+    }
+
+    let should_compress_for_encoding = |is_precompressed_for_encoding: bool, encoding: &str| {
+        accepts_encoding(req, encoding)
+            && allows_compression(compress, is_precompressed_for_encoding, mime_type)
+    };
 
-    if should_compress_for_encoding(file.data_br().is_some(), file.mime_type(), "br") {
+    if should_compress_for_encoding(file.is_precompressed_br(), "br") {
         ShouldCompress::Brotli
-    } else if should_compress_for_encoding(file.data_gzip().is_some(), file.mime_type(), "gzip") {
+    } else if should_compress_for_encoding(file.is_precompressed_gzip(), "gzip") {
         ShouldCompress::Gzip
     } else {
         ShouldCompress::No
     }
 }
 
-fn send_response<T: EmbedRespondable>(
+/// Whether the file is never sent compressed, because of its type or its
+/// extension.
+fn is_excluded_from_compression<T: EmbedRespondable>(
     req: &HttpRequest,
     file: &T,
-    compress: Compress,
-) -> HttpResponse {
-    let mut resp = HttpResponse::Ok();
+    mime_type: Option<&str>,
+    excluded_types: Option<&[String]>,
+) -> bool {
+    let is_excluded = mime_type
+        .map(|mime_type| match excluded_types {
+            Some(excluded_types) => is_excluded_mime_type(mime_type, excluded_types),
+            None => is_excluded_mime_type(mime_type, DEFAULT_COMPRESSION_EXCLUDED_TYPES),
+        })
+        .unwrap_or(false);
+    // A file like `archive.tar.gz` is a gzip file and it's sent as one, with
+    // its own type and no `Content-Encoding`. Compressing it again would make
+    // the client decompress it twice.
+    let name = file.file_name().unwrap_or_else(|| req.path().to_string());
+    is_excluded || has_compressed_extension(&name)
+}
 
-    resp.append_header(("ETag", file.etag().as_ref()));
-    if let Some(last_modified) = file.last_modified() {
-        resp.append_header(("Last-Modified", last_modified.as_ref()));
+/// Whether the compression option allows compressing the file with an
+/// encoding, ignoring what the client accepts.
+fn allows_compression(
+    compress: &Compress,
+    is_precompressed_for_encoding: bool,
+    mime_type: Option<&str>,
+) -> bool {
+    match compress {
+        Compress::Never => false,
+        Compress::IfPrecompressed => is_precompressed_for_encoding,
+        Compress::IfWellKnown => mime_type
+            .map(is_well_known_compressible_mime_type)
+            .unwrap_or(false),
+        Compress::Always => true,
     }
-    if let Some(mime_type) = file.mime_type() {
-        resp.append_header(("Content-Type", mime_type.as_ref()));
+}
+
+/// Whether the file is sent compressed to at least some clients, in which
+/// case the response depends on the `Accept-Encoding` of the request.
+fn may_compress<T: EmbedRespondable>(
+    req: &HttpRequest,
+    file: &T,
+    mime_type: Option<&str>,
+    compress: &Compress,
+    excluded_types: Option<&[String]>,
+) -> bool {
+    !file.data().as_ref().is_empty()
+        && !is_excluded_from_compression(req, file, mime_type, excluded_types)
+        && (allows_compression(compress, file.is_precompressed_br(), mime_type)
+            || allows_compression(compress, file.is_precompressed_gzip(), mime_type))
+}
+
+/// Whether the file is a text file, like HTML, CSS, JavaScript or JSON.
+///
+/// Only the type itself is checked, without parameters like `charset`. Files
+/// without a type, like `rust-embed` files, are guessed from their name or the
+/// request path.
+fn is_text_file<T: EmbedRespondable>(req: &HttpRequest, file: &T, content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    if !essence.is_empty() {
+        return is_well_known_compressible_mime_type(essence);
     }
+    mime_guess::from_path(file.file_name().unwrap_or_else(|| req.path().to_string()))
+        .first_raw()
+        .map(is_well_known_compressible_mime_type)
+        .unwrap_or(false)
+}
 
-    // This doesn't actually mean "no caching", it means revalidate before
-    // using. If we don't add this, web browsers don't try to revalidate assets
-    // like attached scripts and images. The users of this crate may or may not
-    // be using fingerprinting or versioning on their assets, without this their
-    // caching could break.
-    resp.append_header(("Cache-Control", "no-cache"));
+/// The result of evaluating the conditional headers of a request.
+enum Precondition {
+    /// The file should be sent.
+    Passed,
+    /// The client's cached copy is still good, send `304 Not Modified`.
+    NotModified,
+    /// The file has changed since the client last saw it, and the client asked
+    /// for the request to fail in that case. Send `412 Precondition Failed`.
+    Failed,
+}
 
-    if req.method() == Method::HEAD {
-        // For HEAD requests, we only need to send the headers and not the data.
-        resp.finish()
+/// Evaluate the conditional headers in the order that [RFC 9110 section
+/// 13.2.2](https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2) specifies.
+///
+/// `If-Unmodified-Since` is a precondition that is checked first. Then
+/// `If-None-Match` decides whether the client's cached copy is still good, and
+/// only if there was no `If-None-Match` header, `If-Modified-Since` does. Dates
+/// can't be checked for files without a modification time, so those headers
+/// are ignored for them. `If-None-Match: *` matches any file, so it always gets
+/// a 304 for the `GET` and `HEAD` requests that reach this. Without an ETag,
+/// any other `If-None-Match` is ignored instead and `If-Modified-Since` decides.
+fn evaluate_preconditions(
+    req: &HttpRequest,
+    etag: Option<&str>,
+    last_modified_timestamp: Option<i64>,
+) -> Precondition {
+    // Without a timestamp there's nothing to compare the dates against, even if
+    // the file has a formatted `Last-Modified` date. The dates are ignored then,
+    // so the file is sent unless `If-None-Match` says the client has it.
+    let header_date = |name: &str| {
+        last_modified_timestamp.and_then(|last_modified| {
+            req.headers()
+                .get(name)
+                .and_then(parse_http_date)
+                .map(|date| (last_modified, date))
+        })
+    };
+
+    if let Some((last_modified, if_unmodified_since)) = header_date("If-Unmodified-Since") {
+        if last_modified > if_unmodified_since {
+            return Precondition::Failed;
+        }
+    }
+
+    if let Some(matches) = req
+        .headers()
+        .get("If-None-Match")
+        .and_then(|value| match etag {
+            Some(etag) => if_none_match_matches(value, etag),
+            None => is_if_none_match_wildcard(value).then_some(true),
+        })
+    {
+        return if matches {
+            Precondition::NotModified
+        } else {
+            Precondition::Passed
+        };
+    }
+
+    if let Some((last_modified, if_modified_since)) = header_date("If-Modified-Since") {
+        if last_modified <= if_modified_since {
+            return Precondition::NotModified;
+        }
+    }
+
+    Precondition::Passed
+}
+
+/// The `Content-Disposition` header value that makes the browser download the
+/// file, with the file name if it's known.
+fn content_disposition(file_name: Option<String>) -> String {
+    let Some(file_name) = file_name else {
+        return "attachment".to_string();
+    };
+    let quoted = file_name
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => format!("\\{c}"),
+            c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+            _ => "_".to_string(),
+        })
+        .collect::<String>();
+    if file_name.is_ascii() {
+        return format!("attachment; filename=\"{quoted}\"");
+    }
+    // Non-ASCII names need the extended parameter from RFC 6266, the plain
+    // one is kept as a fallback for old clients.
+    let encoded = file_name
+        .bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect::<String>();
+    format!("attachment; filename=\"{quoted}\"; filename*=UTF-8''{encoded}")
+}
+
+/// The key to cache the compressed versions of the file under, or `None` if
+/// the file shouldn't be cached.
+///
+/// This is normally the upstream ETag. Files without one get a hash of their
+/// contents instead, since the weak fallback ETag isn't unique enough to tell
+/// files apart.
+fn compression_cache_key<T: EmbedRespondable>(file: &T) -> Option<String> {
+    if !file.cacheable() {
+        return None;
+    }
+    let etag = file.etag();
+    if is_empty_etag(etag.as_ref()) {
+        Some(compute_etag(EtagAlgorithm::Sha256Hex, file, None))
     } else {
-        // For GET requests, we do send the file body. Depending on whether the
-        // client accepts compressed files or not, we may send the compressed
-        // version.
-        let encoding_choice = should_compress(req, file, &compress);
-        match encoding_choice {
+        Some(etag.as_ref().to_string())
+    }
+}
+
+/// Whether any of the conditional headers of the request are malformed.
+fn has_malformed_conditionals(req: &HttpRequest) -> bool {
+    let headers = req.headers();
+    let malformed_date = |name: &str| {
+        headers
+            .get(name)
+            .map(|v| parse_http_date(v).is_none())
+            .unwrap_or(false)
+    };
+    let malformed_if_range = headers
+        .get("If-Range")
+        .map(|value| match value.to_str() {
+            // If-Range holds either a single ETag or a date.
+            Ok(v) if v.starts_with('"') || v.starts_with("W/") => {
+                v.contains(',') || !is_valid_if_none_match_value(value)
+            }
+            Ok(_) => parse_http_date(value).is_none(),
+            Err(_) => true,
+        })
+        .unwrap_or(false);
+
+    headers
+        .get("If-None-Match")
+        .map(|v| !is_valid_if_none_match_value(v))
+        .unwrap_or(false)
+        || headers
+            .get("Range")
+            .map(|v| !is_valid_range_value(v))
+            .unwrap_or(false)
+        || malformed_date("If-Modified-Since")
+        || malformed_date("If-Unmodified-Since")
+        || malformed_if_range
+}
+
+/// How to respond to the `Range` header of a request.
+enum RangeChoice {
+    /// Send the whole file, either because there was no range request or
+    /// because it can't be honored.
+    Full,
+    /// Send this part of the file. The start and end are inclusive.
+    Partial(u64, u64),
+    /// Send these parts of the file in a `multipart/byteranges` body. They
+    /// are sorted and don't overlap.
+    Multipart(Vec<(u64, u64)>),
+    /// None of the requested range is in the file.
+    Unsatisfiable,
+}
+
+fn range_choice(
+    req: &HttpRequest,
+    etag: &str,
+    last_modified: Option<&str>,
+    len: u64,
+    max_ranges: usize,
+) -> RangeChoice {
+    let Some(ranges) = req.headers().get("Range").and_then(parse_range_value) else {
+        return RangeChoice::Full;
+    };
+    // If-Range means "send me the part I'm missing if the file is still the
+    // one I have, otherwise send me the whole file". Weak ETags never match.
+    if let Some(if_range) = req.headers().get("If-Range").and_then(|v| v.to_str().ok()) {
+        let matches = if if_range.starts_with('"') {
+            if_range == etag
+        } else {
+            last_modified
+                .map(|last_modified| if_range == last_modified)
+                .unwrap_or(false)
+        };
+        if !matches {
+            return RangeChoice::Full;
+        }
+    }
+    // Lots of tiny ranges are a lot of work for very little data, so a
+    // request for too many of them just gets the whole file.
+    if ranges.len() > max_ranges {
+        return RangeChoice::Full;
+    }
+
+    // Ranges that aren't in the file are skipped, and the ones that overlap
+    // or touch are merged so that no part of the file is sent twice.
+    let mut resolved: Vec<(u64, u64)> = ranges.iter().filter_map(|r| r.resolve(len)).collect();
+    resolved.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(resolved.len());
+    for (start, end) in resolved {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    match &merged[..] {
+        [] => RangeChoice::Unsatisfiable,
+        [(start, end)] => RangeChoice::Partial(*start, *end),
+        _ => RangeChoice::Multipart(merged),
+    }
+}
+
+/// The boundary between the parts of a `multipart/byteranges` body.
+///
+/// It's made from the ETag so that it's the same for every response for the
+/// file, which keeps `HEAD` and `GET` responses consistent.
+fn multipart_boundary(etag: &str) -> String {
+    format!("embed-responder-{:08x}", crc32fast::hash(etag.as_bytes()))
+}
+
+/// Build a `multipart/byteranges` body with these parts of the data.
+fn multipart_body(
+    data: &[u8],
+    ranges: &[(u64, u64)],
+    boundary: &str,
+    content_type: Option<&str>,
+) -> Vec<u8> {
+    let len = data.len();
+    let mut body = Vec::new();
+    for &(start, end) in ranges {
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        if let Some(content_type) = content_type {
+            body.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+        }
+        body.extend_from_slice(
+            format!("Content-Range: bytes {start}-{end}/{len}\r\n\r\n").as_bytes(),
+        );
+        body.extend_from_slice(&data[start as usize..=end as usize]);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    body
+}
+
+impl<T: EmbedRespondable> EmbedResponse<T> {
+    /// Finish a response with this body, streaming it if it's larger than the
+    /// auto streaming threshold.
+    ///
+    /// `HEAD` requests get all the same headers, including the
+    /// `Content-Length` of the body, but not the body itself.
+    fn body<B: MessageBody + AsRef<[u8]> + 'static>(
+        &self,
+        req: &HttpRequest,
+        mut resp: HttpResponseBuilder,
+        body: B,
+    ) -> HttpResponse {
+        if req.method() == Method::HEAD {
+            return resp.body(HeadBody(body.as_ref().len() as u64));
+        }
+        if self.is_http10_client(req) {
+            resp.no_chunking(body.as_ref().len() as u64);
+            return resp.body(body);
+        }
+        match self.auto_streaming {
+            Some(threshold) if body.as_ref().len() > threshold => {
+                resp.body(StreamingBody::new(body))
+            }
+            _ => resp.body(body),
+        }
+    }
+
+    /// Finish a response with a compressed body.
+    ///
+    /// The `Content-Length` is set explicitly so that the compressed body is never
+    /// sent with chunked transfer encoding. Some intermediaries mishandle chunked
+    /// bodies that also have a `Content-Encoding`.
+    fn compressed_body<B: MessageBody + AsRef<[u8]> + 'static>(
+        &self,
+        req: &HttpRequest,
+        mut resp: HttpResponseBuilder,
+        body: B,
+    ) -> HttpResponse {
+        resp.no_chunking(body.as_ref().len() as u64);
+        self.body(req, resp, body)
+    }
+
+    /// Append the headers that both full and `304 Not Modified` responses carry:
+    /// the validators and the caching policy.
+    fn append_cache_headers<F: EmbedRespondable>(
+        &self,
+        resp: &mut HttpResponseBuilder,
+        file: &F,
+        etag: Option<&str>,
+    ) {
+        if let Some(etag) = etag {
+            resp.append_header(("ETag", etag));
+        }
+        if let Some(last_modified) = self.last_modified(file) {
+            resp.append_header(("Last-Modified", last_modified));
+        }
+
+        // The default of `no-cache` doesn't actually mean "no caching", it
+        // means revalidate before using. If we don't add this, web browsers
+        // don't try to revalidate assets like attached scripts and images. The
+        // users of this crate may or may not be using fingerprinting or
+        // versioning on their assets, without this their caching could break.
+        let cache_control = self.cache_control.as_deref().unwrap_or("no-cache");
+        let has_no_transform = cache_control
+            .split(',')
+            .any(|directive| directive.trim().eq_ignore_ascii_case("no-transform"));
+        if self.no_transform && !has_no_transform {
+            resp.append_header(("Cache-Control", format!("{cache_control}, no-transform")));
+        } else {
+            resp.append_header(("Cache-Control", cache_control));
+        }
+        if let Some(expires) = self.expires {
+            let now = match &self.clock {
+                Some(clock) => clock.now(),
+                None => SystemClock.now(),
+            };
+            // A duration that doesn't fit into the date is capped instead of
+            // overflowing, and then there's no date to send.
+            let expires = now.saturating_add(i64::try_from(expires.as_secs()).unwrap_or(i64::MAX));
+            if let Some(expires) = format_http_date(expires) {
+                resp.append_header(("Expires", expires));
+            }
+        }
+    }
+
+    /// Append the `Repr-Digest` header, if enabled.
+    ///
+    /// The digest is of the uncompressed file, so this must only be used when
+    /// the file is sent without a `Content-Encoding`.
+    fn append_repr_digest<F: EmbedRespondable>(&self, resp: &mut HttpResponseBuilder, file: &F) {
+        if self.repr_digest {
+            resp.append_header((
+                "Repr-Digest",
+                format!("sha-256=:{}:", BASE64_STANDARD.encode(sha256_hash(file))),
+            ));
+        }
+    }
+
+    /// A `304 Not Modified` response. There's no body, so this must not carry any
+    /// of the headers that describe one like `Content-Encoding` or `Content-Type`.
+    ///
+    /// It does carry the same `Vary` as the full response, so that caches
+    /// keep matching it to the same requests.
+    fn not_modified<F: EmbedRespondable>(
+        &self,
+        req: &HttpRequest,
+        file: &F,
+        etag: Option<&str>,
+    ) -> HttpResponse {
+        let mut resp = HttpResponse::NotModified();
+        self.append_cache_headers(&mut resp, file, etag);
+        if let Some(vary) = self.vary(req, file) {
+            resp.append_header(("Vary", vary));
+        }
+        resp.finish()
+    }
+
+    fn send_response<F: EmbedRespondable>(
+        &self,
+        req: &HttpRequest,
+        file: &F,
+        etag: &str,
+    ) -> HttpResponse {
+        let mut resp = HttpResponse::build(self.status.unwrap_or(StatusCode::OK));
+
+        self.append_cache_headers(&mut resp, file, Some(etag));
+        for link in &self.early_hints {
+            resp.append_header(("Link", link.to_string()));
+        }
+        if let Some(origin) = &self.timing_allow_origin {
+            resp.append_header(("Timing-Allow-Origin", origin.as_str()));
+        }
+        let mime_type = self.content_type(req, file);
+        if let (true, Some(mime_type)) = (self.content_type_guard, &mime_type) {
+            if conflicts_with_content_type(mime_type, file.data().as_ref()) {
+                return HttpResponse::InternalServerError().finish();
+            }
+        }
+        if let Some(mime_type) = &mime_type {
+            resp.append_header(("Content-Type", mime_type.as_str()));
+        }
+        if let Some(vary) = self.vary(req, file) {
+            resp.append_header(("Vary", vary));
+        }
+
+        if let Some(attachment) = &self.attachment {
+            resp.append_header((
+                "Content-Disposition",
+                content_disposition(attachment.clone().or_else(|| file.file_name())),
+            ));
+        }
+
+        // The edge in front of us will serve the file itself, including any
+        // ranges or compression, so we only send the headers.
+        if let Some((name, path)) = &self.offload_header {
+            resp.append_header((name.as_str(), path(req)));
+            return resp.finish();
+        }
+
+        let data = file.data();
+        let len = data.as_ref().len() as u64;
+
+        // Depending on whether the client accepts compressed files or not, we
+        // may send the compressed version. HEAD requests go through the same
+        // negotiation, so that they get the same headers the GET would.
+        let encoding_choice = self.encoding_choice(req, file, mime_type.as_deref(), len);
+
+        // There's nothing we can send if the client can't take the file
+        // compressed and we were asked not to send it uncompressed.
+        if self.require_compression
+            && len > 0
+            && !self.is_uncompressed_peer(req)
+            && matches!(encoding_choice, ShouldCompress::No)
+        {
+            return HttpResponse::NotAcceptable().finish();
+        }
+
+        // Range requests are served from the uncompressed file, since the
+        // ranges the client asks for are offsets into the uncompressed data.
+        // Unless the policy says otherwise, a range wins over compression.
+        // Pages served with another status, like error pages, and files for
+        // HTTP/1.0 clients in compatibility mode are always sent whole.
+        let range = if self.status.is_some() || self.is_http10_client(req) {
+            RangeChoice::Full
+        } else if !self.ranges {
+            resp.append_header(("Accept-Ranges", "none"));
+            RangeChoice::Full
+        } else {
+            resp.append_header(("Accept-Ranges", "bytes"));
+            match (self.range_compression_policy, &encoding_choice) {
+                (
+                    RangeCompressionPolicy::PreferCompression,
+                    ShouldCompress::Brotli | ShouldCompress::Gzip,
+                ) => RangeChoice::Full,
+                _ => range_choice(
+                    req,
+                    etag,
+                    self.last_modified(file).as_deref(),
+                    len,
+                    self.max_ranges,
+                ),
+            }
+        };
+        match range {
+            RangeChoice::Partial(start, end) => {
+                resp.status(StatusCode::PARTIAL_CONTENT);
+                self.append_repr_digest(&mut resp, file);
+                resp.append_header(("Content-Range", format!("bytes {start}-{end}/{len}")));
+                return self.body(
+                    req,
+                    resp,
+                    Bytes::copy_from_slice(&data.as_ref()[start as usize..=end as usize]),
+                );
+            }
+            RangeChoice::Multipart(ranges) => {
+                resp.status(StatusCode::PARTIAL_CONTENT);
+                self.append_repr_digest(&mut resp, file);
+                let boundary = multipart_boundary(etag);
+                resp.insert_header((
+                    "Content-Type",
+                    format!("multipart/byteranges; boundary={boundary}"),
+                ));
+                return self.body(
+                    req,
+                    resp,
+                    multipart_body(data.as_ref(), &ranges, &boundary, mime_type.as_deref()),
+                );
+            }
+            RangeChoice::Unsatisfiable => {
+                resp.status(StatusCode::RANGE_NOT_SATISFIABLE);
+                resp.append_header(("Content-Range", format!("bytes */{len}")));
+                return resp.finish();
+            }
+            RangeChoice::Full => {}
+        }
+
+        if let (Some(name), false) = (
+            &self.uncompressed_length_header,
+            matches!(encoding_choice, ShouldCompress::No),
+        ) {
+            resp.append_header((name.as_str(), len.to_string()));
+        }
+        let mut resp = match encoding_choice {
             ShouldCompress::Brotli => {
                 resp.append_header(("Content-Encoding", "br"));
                 match file.data_br() {
-                    Some(data_br) => resp.body(data_br),
-                    None => resp.body(compress_data_br(file.etag().as_ref(), file.data().as_ref())),
+                    Some(data_br) => self.compressed_body(req, resp, data_br),
+                    None => self.compressed_body(
+                        req,
+                        resp,
+                        compress_data_br(
+                            compression_cache_key(file).as_deref(),
+                            file.data().as_ref(),
+                        ),
+                    ),
                 }
             }
             ShouldCompress::Gzip => {
                 resp.append_header(("Content-Encoding", "gzip"));
                 match file.data_gzip() {
-                    Some(data_gzip) => resp.body(data_gzip),
-                    None => resp.body(compress_data_gzip(
-                        file.etag().as_ref(),
-                        file.data().as_ref(),
-                    )),
+                    Some(data_gzip) => self.compressed_body(req, resp, data_gzip),
+                    None => self.compressed_body(
+                        req,
+                        resp,
+                        compress_data_gzip(
+                            compression_cache_key(file).as_deref(),
+                            file.data().as_ref(),
+                        ),
+                    ),
+                }
+            }
+            ShouldCompress::No => {
+                self.append_repr_digest(&mut resp, file);
+                return self.body(req, resp, data);
+            }
+        };
+        match self.response_etag(etag, &encoding_choice) {
+            Some(response_etag) if response_etag != etag => {
+                if let Ok(value) = HeaderValue::try_from(response_etag) {
+                    resp.headers_mut().insert(ETAG, value);
                 }
             }
-            ShouldCompress::No => resp.body(file.data()),
+            Some(_) => {}
+            None => {
+                resp.headers_mut().remove(ETAG);
+            }
+        }
+        resp
+    }
+
+    /// The `Vary` header for the file: `Accept-Encoding` if the file may be
+    /// sent compressed to some clients, and the headers added with
+    /// `with_vary`.
+    fn vary<F: EmbedRespondable>(&self, req: &HttpRequest, file: &F) -> Option<String> {
+        let mime_type = self.content_type(req, file);
+        let mut vary: Vec<&str> = Vec::new();
+        if may_compress(
+            req,
+            file,
+            mime_type.as_deref(),
+            &self.compress.unwrap_or_default(),
+            self.gzip_excluded_types.as_deref(),
+        ) {
+            vary.push("Accept-Encoding");
+        }
+        for name in &self.vary {
+            if !vary.iter().any(|v| v.eq_ignore_ascii_case(name)) {
+                vary.push(name);
+            }
         }
+        (!vary.is_empty()).then(|| vary.join(", "))
+    }
+
+    /// Whether to send the file compressed, and with which encoding.
+    ///
+    /// An empty file is always sent as it is. Compressing it would only make
+    /// the body larger than nothing. Clients on the same machine can be
+    /// skipped too if asked, since bandwidth is free for them.
+    fn encoding_choice<F: EmbedRespondable>(
+        &self,
+        req: &HttpRequest,
+        file: &F,
+        mime_type: Option<&str>,
+        len: u64,
+    ) -> ShouldCompress {
+        if len == 0 || self.is_uncompressed_peer(req) {
+            return ShouldCompress::No;
+        }
+        let compress = self.compress.unwrap_or_default();
+        let excluded_types = self.gzip_excluded_types.as_deref();
+        let can_compress = |is_precompressed_for_encoding: bool| {
+            !is_excluded_from_compression(req, file, mime_type, excluded_types)
+                && allows_compression(&compress, is_precompressed_for_encoding, mime_type)
+        };
+        match self.forced_encoding {
+            Some(Encoding::Identity) => return ShouldCompress::No,
+            Some(Encoding::Gzip) if can_compress(file.is_precompressed_gzip()) => {
+                return ShouldCompress::Gzip
+            }
+            Some(Encoding::Br) if can_compress(file.is_precompressed_br()) => {
+                return ShouldCompress::Brotli
+            }
+            Some(_) => return ShouldCompress::No,
+            None => {}
+        }
+        match should_compress(req, file, mime_type, &compress, excluded_types) {
+            ShouldCompress::No if self.force_gzip && can_compress(file.is_precompressed_gzip()) => {
+                ShouldCompress::Gzip
+            }
+            choice => choice,
+        }
+    }
+
+    /// The ETag of the response when it's sent with this encoding, following
+    /// the ETag mode.
+    fn response_etag(&self, etag: &str, encoding_choice: &ShouldCompress) -> Option<String> {
+        match (self.etag_mode, encoding_choice.encoding()) {
+            (EtagMode::Shared, _) | (_, None) => Some(etag.to_string()),
+            (EtagMode::PerEncoding, Some(encoding)) => Some(encoding_etag(etag, encoding)),
+            (EtagMode::NoneOnCompressed, Some(_)) => None,
+        }
+    }
+
+    /// The `Content-Type` to send with the file.
+    ///
+    /// A type set on the response wins, then a type registered in the app's
+    /// `MimeRegistry` for the file's extension, then the embed's own guess. If
+    /// there's still no useful type and the request path fallback is enabled,
+    /// the type is guessed from the request path instead.
+    fn content_type<F: EmbedRespondable>(&self, req: &HttpRequest, file: &F) -> Option<String> {
+        if let Some(content_type) = &self.content_type {
+            return Some(content_type.clone());
+        }
+        // rust-embed files don't know their own name, so the request path is
+        // the best we can do for them.
+        if let Some(registered) = MimeRegistry::from_app_data(req).and_then(|registry| {
+            registry.get(&file.file_name().unwrap_or_else(|| req.path().to_string()))
+        }) {
+            return Some(registered.to_string());
+        }
+
+        let mime_type = file.mime_type().map(|v| v.as_ref().to_string());
+        if !self.content_type_from_request_path {
+            return mime_type;
+        }
+        match mime_type.as_deref() {
+            None | Some("application/octet-stream") => mime_guess::from_path(req.path())
+                .first_raw()
+                .map(ToOwned::to_owned)
+                .or(mime_type),
+            _ => mime_type,
+        }
+    }
+
+    /// Whether compression should be skipped because the client is connecting
+    /// over the loopback interface.
+    fn is_uncompressed_peer(&self, req: &HttpRequest) -> bool {
+        self.skip_compression_on_loopback
+            && req
+                .peer_addr()
+                .map(|addr| addr.ip().is_loopback())
+                .unwrap_or(false)
+    }
+
+    /// Whether the request is from an HTTP/1.0 client that should get the
+    /// simplest possible response.
+    fn is_http10_client(&self, req: &HttpRequest) -> bool {
+        self.http10_compat && req.version() == Version::HTTP_10
+    }
+
+    /// When the file was last modified, either as set on the response or as
+    /// the embed knows it.
+    fn last_modified_timestamp<F: EmbedRespondable>(&self, file: &F) -> Option<i64> {
+        self.last_modified_timestamp
+            .or_else(|| file.last_modified_timestamp())
+    }
+
+    /// The `Last-Modified` header value for the file.
+    fn last_modified<F: EmbedRespondable>(&self, file: &F) -> Option<String> {
+        let upstream = || file.last_modified().map(|v| v.as_ref().to_string());
+        match self.last_modified_timestamp {
+            Some(timestamp) => format_http_date(timestamp),
+            None if self.reformat_last_modified => file
+                .last_modified_timestamp()
+                .and_then(format_http_date)
+                .or_else(upstream),
+            // Files that only know the timestamp still get a `Last-Modified`,
+            // and with it date conditionals and `If-Range` dates.
+            None => {
+                upstream().or_else(|| file.last_modified_timestamp().and_then(format_http_date))
+            }
+        }
+    }
+
+    /// The ETag of the file, computed with the selected algorithm.
+    fn file_etag<F: EmbedRespondable>(&self, file: &F) -> String {
+        compute_etag(
+            self.etag_algorithm.unwrap_or_default(),
+            file,
+            self.last_modified_timestamp(file),
+        )
+    }
+
+    /// Fill in the settings this response didn't set from the app-wide
+    /// defaults, if any have been registered.
+    fn apply_defaults(mut self, req: &HttpRequest) -> Self {
+        if let Some(defaults) = ResponderDefaults::from_app_data(req) {
+            self.compress = self.compress.or(defaults.compress);
+            self.etag_algorithm = self.etag_algorithm.or(defaults.etag_algorithm);
+            self.cache_control = self
+                .cache_control
+                .or_else(|| defaults.cache_control.clone());
+            self.method_not_allowed_body = self
+                .method_not_allowed_body
+                .or_else(|| defaults.method_not_allowed_body.clone());
+        }
+        self
     }
 }
 
@@ -142,72 +1014,746 @@ impl<T: EmbedRespondable> Responder for EmbedResponse<T> {
     type Body = BoxBody;
 
     fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
-        match self.file {
+        #[cfg(feature = "tracing")]
+        let span = crate::serve_info::response_span(req);
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+
+        let mut this = self.apply_defaults(req);
+        let on_response = this.on_response.take();
+        let mut resp = this.respond(req);
+        // Caching an error would keep the file missing for the client even
+        // after it has been deployed.
+        if this.no_store_errors
+            && (resp.status().is_client_error() || resp.status().is_server_error())
+        {
+            resp.headers_mut()
+                .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        }
+        // Network errors can happen with any response, so these go on every
+        // one of them rather than just the ones with the file.
+        for (name, value) in [("nel", &this.nel), ("report-to", &this.report_to)] {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                resp.headers_mut()
+                    .insert(HeaderName::from_static(name), value);
+            }
+        }
+        // Only the responses with the file describe it. The body of the others,
+        // like a 405 with a custom body, keeps its headers.
+        let is_file_response = matches!(
+            resp.status(),
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT | StatusCode::NOT_MODIFIED
+        );
+        if let (Some(managed_headers), true) = (&this.managed_headers, is_file_response) {
+            for name in MANAGED_HEADERS {
+                let is_multipart = *name == "Content-Type"
+                    && resp
+                        .headers()
+                        .get(CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .is_some_and(|v| v.starts_with("multipart/byteranges"));
+                if !is_multipart
+                    && !managed_headers
+                        .iter()
+                        .any(|managed| managed.eq_ignore_ascii_case(name))
+                {
+                    resp.headers_mut().remove(*name);
+                }
+            }
+        }
+        #[cfg(feature = "tracing")]
+        crate::serve_info::record_response(&span, &ServeInfo::new(req, &resp));
+        if let Some(on_response) = on_response {
+            on_response(&ServeInfo::new(req, &resp));
+        }
+        resp
+    }
+}
+
+impl<T: EmbedRespondable> EmbedResponse<T> {
+    /// Create the response to the request, after the defaults have been applied.
+    fn respond(&self, req: &HttpRequest) -> HttpResponse {
+        if let Some(location) = &self.redirect {
+            return HttpResponse::MovedPermanently()
+                .insert_header(("Location", location.as_str()))
+                .finish();
+        }
+        match &self.file {
             Some(file) => {
                 // This responder can't respond to anything other than GET and HEAD requests.
                 if req.method() != Method::GET && req.method() != Method::HEAD {
-                    return HttpResponse::NotImplemented().finish();
+                    let mut resp = HttpResponse::MethodNotAllowed();
+                    resp.append_header(("Allow", "GET, HEAD"));
+                    return match &self.method_not_allowed_body {
+                        Some((body, mime_type)) => {
+                            resp.append_header(("Content-Type", mime_type.as_str()));
+                            resp.body(body.clone())
+                        }
+                        None => resp.finish(),
+                    };
                 }
 
-                // For the ETag we are using the sha256 hash of the file, encoded with
-                // base64. We surround it with quotes as per the spec.
-                let e = file.etag();
-                let etag = e.as_ref();
-
-                let last_modified_timestamp = file.last_modified_timestamp();
-
-                // Handle If-None-Match condition. If the client has the file cached
-                // already, it can send back the ETag to ask for the file only if it has
-                // changed.
-                //
-                // We first check If-None-Match because the spec specifies that it gets
-                // priority over If-Modified-Since.
-                if let Some(req_etags) = req
-                    .headers()
-                    .get("If-None-Match")
-                    .and_then(parse_if_none_match_value)
-                {
-                    if req_etags.contains(&etag) {
-                        return HttpResponse::NotModified().finish();
-                    } else {
-                        return send_response(req, &file, self.compress);
-                    }
+                if self.strict_conditionals && has_malformed_conditionals(req) {
+                    return HttpResponse::BadRequest().finish();
                 }
-                // If there was no `If-None-Match` condition, check for
-                // `If-Unmodified-Since` condition next. As a fallback to ETag,
-                // the client can also check if a file has been modified using
-                // the last modified time of the file.
-                if let Some(last_modified_timestamp) = last_modified_timestamp {
-                    if let Some(if_unmodified_since) = req
-                        .headers()
-                        .get("If-Unmodified-Since")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
-                    {
-                        // It's been modified since then
-                        if last_modified_timestamp > if_unmodified_since.timestamp() {
-                            return send_response(req, &file, self.compress);
-                        } else {
-                            return HttpResponse::NotModified().finish();
-                        }
+
+                if self.base_path.is_some() || self.strip_bom {
+                    let content_type = self.content_type(req, file).unwrap_or_default();
+                    let changes = Rewrite {
+                        base_path: self
+                            .base_path
+                            .as_deref()
+                            .filter(|_| content_type.starts_with("text/html")),
+                        strip_bom: self.strip_bom && is_text_file(req, file, &content_type),
+                    };
+                    if let Some(rewritten) = rewrite_file(file, changes) {
+                        return self.respond_file(req, &rewritten);
                     }
                 }
-                // If there was no `If-Unmodified-Since` header either, that
-                // means the client does not have this file cached.
-                send_response(req, &file, self.compress)
+                self.respond_file(req, file)
             }
             None => HttpResponse::NotFound().finish(),
         }
     }
+
+    /// Respond with the file, which is either the file of this response or a
+    /// version of it that has been rewritten.
+    fn respond_file<F: EmbedRespondable>(&self, req: &HttpRequest, file: &F) -> HttpResponse {
+        // By default the ETag is the sha256 hash of the file, which the embed
+        // has already computed for us.
+        let mut etag = self.file_etag(file);
+        // A proxy may transform the response, which a strong ETag would claim
+        // can't happen.
+        if self.weak_etag_on_via && req.headers().contains_key("Via") && !etag.starts_with("W/") {
+            etag.insert_str(0, "W/");
+        }
+        let etag = etag.as_str();
+        // The client's cached copy is compared with the ETag of the response
+        // it would get now, which may depend on the encoding.
+        let response_etag = match self.etag_mode {
+            EtagMode::Shared => Some(etag.to_string()),
+            _ => {
+                let len = file.data().as_ref().len() as u64;
+                let mime_type = self.content_type(req, file);
+                let encoding_choice = self.encoding_choice(req, file, mime_type.as_deref(), len);
+                self.response_etag(etag, &encoding_choice)
+            }
+        };
+
+        // Conditional requests only make sense for the file itself, not for
+        // pages like a 404 that happen to be served from a file. Old HTTP/1.0
+        // clients may not handle a 304 well, so they can be skipped too.
+        if self.status.is_some() || self.is_http10_client(req) {
+            return self.send_response(req, file, etag);
+        }
+
+        match evaluate_preconditions(
+            req,
+            response_etag.as_deref(),
+            self.last_modified_timestamp(file),
+        ) {
+            Precondition::Failed => HttpResponse::PreconditionFailed().finish(),
+            Precondition::NotModified => self.not_modified(req, file, response_etag.as_deref()),
+            Precondition::Passed => self.send_response(req, file, etag),
+        }
+    }
 }
 
 impl<T: EmbedRespondable> EmbedResponse<T> {
+    /// Create a response for the file, or a 404 if it's `None`.
+    ///
+    /// This is what `.into_response()` does. It's a `const fn`, so the response
+    /// for a file that is known at compile time can be created in a `const`
+    /// context too.
+    pub const fn new(file: Option<T>) -> Self {
+        EmbedResponse {
+            file,
+            compress: None,
+            gzip_excluded_types: None,
+            cache_control: None,
+            etag_algorithm: None,
+            method_not_allowed_body: None,
+            content_type_from_request_path: false,
+            offload_header: None,
+            skip_compression_on_loopback: false,
+            uncompressed_length_header: None,
+            timing_allow_origin: None,
+            expires: None,
+            strict_conditionals: false,
+            content_type: None,
+            last_modified_timestamp: None,
+            status: None,
+            auto_streaming: None,
+            repr_digest: false,
+            attachment: None,
+            on_response: None,
+            early_hints: Vec::new(),
+            base_path: None,
+            range_compression_policy: RangeCompressionPolicy::PreferRange,
+            content_type_guard: false,
+            no_transform: false,
+            http10_compat: false,
+            require_compression: false,
+            reformat_last_modified: false,
+            etag_mode: EtagMode::Shared,
+            managed_headers: None,
+            strip_bom: false,
+            clock: None,
+            no_store_errors: true,
+            vary: Vec::new(),
+            max_ranges: DEFAULT_MAX_RANGES,
+            nel: None,
+            report_to: None,
+            force_gzip: false,
+            redirect: None,
+            weak_etag_on_via: false,
+            forced_encoding: None,
+            ranges: true,
+        }
+    }
+
     /// Set the compression option to use for this response. Please see the
     /// Compress type for allowed options.
     pub fn use_compression(mut self, option: Compress) -> Self {
-        self.compress = option;
+        self.compress = Some(option);
         self
     }
+
+    /// Respond with `406 Not Acceptable` instead of sending the file
+    /// uncompressed, when it can't be compressed for the client.
+    ///
+    /// This protects the bandwidth of constrained devices serving large
+    /// assets. A file can't be compressed if the client doesn't accept gzip or
+    /// brotli, but also if the compression settings of the response don't
+    /// allow it, for example for mime types that are excluded from
+    /// compression. Empty files, and clients skipped with
+    /// `with_skip_compression_on_loopback`, are still sent uncompressed. This
+    /// is off by default.
+    pub fn with_require_compression(mut self, enabled: bool) -> Self {
+        self.require_compression = enabled;
+        self
+    }
+
+    /// Turn range requests on or off for this response.
+    ///
+    /// With ranges turned off, the response has `Accept-Ranges: none` and the
+    /// `Range` header of the request is ignored, so the whole file is always
+    /// sent with `200 OK`. This is for files where a part of the file is
+    /// meaningless, or where the body is changed before it's sent. Ranges are
+    /// on by default.
+    pub fn with_ranges(mut self, enabled: bool) -> Self {
+        self.ranges = enabled;
+        self
+    }
+
+    /// Set how many ranges a single request can ask for.
+    ///
+    /// A request for several ranges gets them all in a `multipart/byteranges`
+    /// body. Since every range has some overhead, a request for too many
+    /// ranges is served the whole file with `200 OK` instead. The default
+    /// is 10.
+    pub fn with_max_ranges(mut self, max_ranges: usize) -> Self {
+        self.max_ranges = max_ranges;
+        self
+    }
+
+    /// Choose between serving a range uncompressed, or the whole file
+    /// compressed, when a request asks for a range and accepts compression.
+    ///
+    /// The default is `RangeCompressionPolicy::PreferRange`, which sends the
+    /// range uncompressed with `206 Partial Content`.
+    /// `RangeCompressionPolicy::PreferCompression` ignores the range and sends
+    /// the whole file compressed with `200 OK` instead, if the file would be
+    /// compressed for this request.
+    pub fn with_range_compression_policy(mut self, policy: RangeCompressionPolicy) -> Self {
+        self.range_compression_policy = policy;
+        self
+    }
+
+    /// Set the mime types that are never sent compressed, even if a
+    /// precompressed version is available or `Compress::Always` is used.
+    ///
+    /// By default this is a list of common formats that are already
+    /// compressed, like `image/png`, `image/jpeg`, `font/woff2` and
+    /// `application/zip`. Pass an empty list to allow compressing everything,
+    /// other than files with the extension of a compressed format like `.gz`
+    /// or `.br`. Those are never compressed again.
+    pub fn with_gzip_excluded_types(mut self, types: &[&str]) -> Self {
+        self.gzip_excluded_types = Some(types.iter().map(|v| v.to_string()).collect());
+        self
+    }
+
+    /// Send files uncompressed to clients connecting from a loopback address,
+    /// like `127.0.0.1` or `::1`.
+    ///
+    /// Compressing the response saves no bandwidth for these clients and only
+    /// costs CPU time, which matters for sidecars and local development. This
+    /// is off by default. Note that behind a reverse proxy on the same machine,
+    /// every client will look like a loopback client.
+    pub fn with_skip_compression_on_loopback(mut self, skip: bool) -> Self {
+        self.skip_compression_on_loopback = skip;
+        self
+    }
+
+    /// Send the size of the uncompressed file in an
+    /// `X-Uncompressed-Content-Length` header when the file is sent compressed.
+    ///
+    /// This is not a standard header and it's informational only, but some
+    /// download UIs use it to show progress. This is off by default.
+    pub fn with_uncompressed_length_header(mut self, enabled: bool) -> Self {
+        self.uncompressed_length_header =
+            enabled.then(|| "X-Uncompressed-Content-Length".to_string());
+        self
+    }
+
+    /// Like `with_uncompressed_length_header`, but with a custom header name.
+    pub fn with_uncompressed_length_header_name(mut self, name: impl Into<String>) -> Self {
+        self.uncompressed_length_header = Some(name.into());
+        self
+    }
+
+    /// Send the file with this status instead of `200 OK`, for example to
+    /// serve an error page.
+    ///
+    /// Responses with a custom status are always sent in full: conditional
+    /// and range requests are ignored for them.
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Stream bodies that are larger than `threshold` bytes, instead of sending
+    /// them all at once.
+    ///
+    /// Streamed bodies are sent in 64 KiB chunks, which keeps a large file
+    /// from holding up the connection all at once. Smaller bodies are sent in
+    /// one piece. The threshold applies to the body that is actually sent, so
+    /// for compressed responses it's the compressed size, and for range
+    /// requests it's the size of the range.
+    pub fn with_auto_streaming(mut self, threshold: usize) -> Self {
+        self.auto_streaming = Some(threshold);
+        self
+    }
+
+    /// Serve HTTP/1.0 clients the whole file with `200 OK` and a
+    /// `Content-Length`, ignoring conditional and range requests.
+    ///
+    /// Some legacy clients, like the ones on embedded devices, speak HTTP/1.0
+    /// and don't handle `304 Not Modified` or partial responses well. With
+    /// this enabled, their conditional headers are ignored and the body is
+    /// never streamed. Clients using newer versions of HTTP are served as
+    /// usual. This is off by default.
+    pub fn with_http10_compat(mut self, enabled: bool) -> Self {
+        self.http10_compat = enabled;
+        self
+    }
+
+    /// Make browsers download the file instead of displaying it, by sending
+    /// `Content-Disposition: attachment`.
+    ///
+    /// The name of the file is included if it's known, which it isn't for
+    /// `rust-embed` files. Use `with_attachment_name` to set it yourself.
+    pub fn with_attachment(mut self) -> Self {
+        self.attachment = Some(None);
+        self
+    }
+
+    /// Like `with_attachment`, but with the name the browser should save the
+    /// file as.
+    pub fn with_attachment_name(mut self, file_name: impl Into<String>) -> Self {
+        self.attachment = Some(Some(file_name.into()));
+        self
+    }
+
+    /// Set up the response for serving downloads.
+    ///
+    /// The file is sent as an attachment, and never compressed so that the
+    /// downloaded file is exactly the embedded one, and so that range requests
+    /// can resume interrupted downloads. Ranges are turned on even if they were
+    /// turned off with `with_ranges` before.
+    pub fn download(self) -> Self {
+        self.with_attachment()
+            .use_compression(Compress::Never)
+            .with_ranges(true)
+    }
+
+    /// Set the `Cache-Control` header to send with the file.
+    ///
+    /// The default is `no-cache`, which lets clients cache the file but makes
+    /// them revalidate it before every use. If your assets are fingerprinted,
+    /// you might want something like `public, max-age=31536000, immutable`.
+    pub fn with_cache_control(mut self, value: impl Into<String>) -> Self {
+        self.cache_control = Some(value.into());
+        self
+    }
+
+    /// Send errors, like the 404 for a missing file or an error page served
+    /// with `with_status`, with `Cache-Control: no-store`.
+    ///
+    /// This is on by default, so that browsers and CDNs don't hold on to a
+    /// 404 after the file has been deployed. The `Cache-Control` set with
+    /// `with_cache_control` is only used for errors when this is turned off.
+    pub fn with_no_cache_for_errors(mut self, enabled: bool) -> Self {
+        self.no_store_errors = enabled;
+        self
+    }
+
+    /// Add `no-transform` to the `Cache-Control` header, to tell proxies and
+    /// CDNs not to change the file on its way to the client, for example by
+    /// recompressing images or minifying scripts.
+    ///
+    /// Use this for files that must reach the client byte for byte, like
+    /// signed bundles or files checked with subresource integrity. This is
+    /// added on top of the `Cache-Control` value of the response, unless that
+    /// already has `no-transform`.
+    pub fn with_no_transform(mut self, enabled: bool) -> Self {
+        self.no_transform = enabled;
+        self
+    }
+
+    /// Add these headers to the `Vary` header of the response, for when the
+    /// handler picks the file based on them, like `Accept-Language`.
+    ///
+    /// `Vary: Accept-Encoding` is sent without this whenever the file may be
+    /// sent compressed. The `Vary` header is sent with `304 Not Modified`
+    /// responses too.
+    pub fn with_vary(mut self, headers: &[&str]) -> Self {
+        self.vary = headers.iter().map(|v| v.to_string()).collect();
+        self
+    }
+
+    /// Set the `Timing-Allow-Origin` header to send with the file, for example
+    /// `*` or a specific origin.
+    ///
+    /// Without this header, pages on other origins can only see limited
+    /// Resource Timing API information for the file.
+    pub fn with_timing_allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.timing_allow_origin = Some(origin.into());
+        self
+    }
+
+    /// Send the file with this encoding, no matter what the `Accept-Encoding`
+    /// header of the request says.
+    ///
+    /// This is for caches and edge servers that store every encoding of a file
+    /// separately and ask for the one they need. The compression option and
+    /// the types excluded from compression still apply: if the file can't be
+    /// sent with the encoding, for example because it's `Compress::Never` or
+    /// an image, it's sent uncompressed instead.
+    pub fn with_forced_encoding(mut self, encoding: Encoding) -> Self {
+        self.forced_encoding = Some(encoding);
+        self
+    }
+
+    /// Send a weak ETag when the request came through a proxy, and the usual
+    /// strong ETag otherwise.
+    ///
+    /// Proxies add a `Via` header, and one that recompresses or otherwise
+    /// transforms responses should weaken the ETag but may not. With this
+    /// enabled, requests with a `Via` header get the ETag with a `W/` prefix.
+    /// Weak ETags still work for `If-None-Match`, but never for `If-Range`, so
+    /// range requests through a proxy only get a part of the file when they
+    /// don't send `If-Range`. This is off by default.
+    pub fn with_auto_weak_etag_on_via(mut self, enabled: bool) -> Self {
+        self.weak_etag_on_via = enabled;
+        self
+    }
+
+    /// Send the file gzip compressed even to clients that didn't say they
+    /// accept gzip, as long as the compression option allows compressing it.
+    ///
+    /// **Warning:** this goes against the HTTP specification, and clients that
+    /// can't decompress gzip will get a body they can't read. Only turn this on
+    /// when you know every client, like for an internal service. Clients that
+    /// accept brotli still get brotli. This is off by default.
+    pub fn with_force_gzip(mut self, force_gzip: bool) -> Self {
+        self.force_gzip = force_gzip;
+        self
+    }
+
+    /// Set the `NEL` header, which turns on Network Error Logging for the
+    /// origin.
+    ///
+    /// The value is a JSON object that is sent as is, naming a group from the
+    /// `Report-To` header and how long the policy lasts in seconds, like
+    /// `{"report_to":"default","max_age":2592000}`. An empty value sends no
+    /// header. The header is sent with every response, including errors and
+    /// `304 Not Modified`.
+    pub fn with_nel(mut self, json: impl Into<String>) -> Self {
+        self.nel = Some(json.into()).filter(|json| !json.trim().is_empty());
+        self
+    }
+
+    /// Set the `Report-To` header, which tells the browser where to send
+    /// reports, like the ones `with_nel` turns on.
+    ///
+    /// The value is a JSON object that is sent as is, like
+    /// `{"group":"default","max_age":2592000,"endpoints":[{"url":"https://example.com/reports"}]}`.
+    /// An empty value sends no header. The header is sent with every response,
+    /// including errors and `304 Not Modified`.
+    pub fn with_report_to(mut self, json: impl Into<String>) -> Self {
+        self.report_to = Some(json.into()).filter(|json| !json.trim().is_empty());
+        self
+    }
+
+    /// Send an `Expires` header, set to this far in the future from the time
+    /// of the response.
+    ///
+    /// This is for HTTP/1.0 caches and proxies that don't understand
+    /// `Cache-Control`. It's fine to set both, clients that understand
+    /// `Cache-Control: max-age` will use that instead.
+    pub fn with_expires(mut self, duration: Duration) -> Self {
+        self.expires = Some(duration);
+        self
+    }
+
+    /// Use this clock instead of the system clock for the headers that depend
+    /// on the current time, like `Expires`.
+    ///
+    /// This is mostly useful for tests, see `FixedClock`.
+    pub fn with_clock(mut self, clock: impl Clock + Send + Sync + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Respond with `400 Bad Request` if any of the conditional headers, like
+    /// `If-None-Match` or `Range`, can't be parsed.
+    ///
+    /// By default malformed conditional headers are ignored, and the request
+    /// is answered as if they weren't there.
+    pub fn with_strict_conditionals(mut self, strict: bool) -> Self {
+        self.strict_conditionals = strict;
+        self
+    }
+
+    /// Set the UNIX timestamp of when the file was last modified, instead of
+    /// using the one from the embed.
+    ///
+    /// The timestamps in the embed come from the files at build time, which
+    /// isn't useful with reproducible builds that zero them out. You could use
+    /// the deploy time or the commit time instead. This is used both for the
+    /// `Last-Modified` header and the date conditionals like
+    /// `If-Modified-Since`.
+    pub fn with_last_modified_timestamp(mut self, timestamp: i64) -> Self {
+        self.last_modified_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Format the `Last-Modified` header from the modification time of the
+    /// file, instead of using the date string that the embed formatted.
+    ///
+    /// The header is then always an IMF-fixdate like
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`, as RFC 9110 requires, whatever format
+    /// the embed uses. Strict clients may reject other formats. Files without
+    /// a modification time keep the embed's string. This is off by default.
+    pub fn with_reformatted_last_modified(mut self, enabled: bool) -> Self {
+        self.reformat_last_modified = enabled;
+        self
+    }
+
+    /// Send a `Repr-Digest` header with the sha256 hash of the file, as
+    /// described in [RFC 9530](https://www.rfc-editor.org/rfc/rfc9530).
+    ///
+    /// Both `rust-embed` and `rust-embed-for-web` have already computed the
+    /// hash, so this is cheap. The digest is only sent when the file is sent
+    /// uncompressed, since the hash is of the uncompressed file.
+    pub fn with_repr_digest(mut self, enabled: bool) -> Self {
+        self.repr_digest = enabled;
+        self
+    }
+
+    /// Tell the browser about resources it will need, like the stylesheets
+    /// and scripts of an HTML page, so it can start fetching them early.
+    ///
+    /// Ideally these would be sent in a `103 Early Hints` response before the
+    /// file, but `actix-web` has no way to send informational responses. They
+    /// are sent as `Link` headers on the response instead, which browsers
+    /// still act on as soon as they get the headers.
+    pub fn with_early_hints(mut self, links: Vec<LinkHeader>) -> Self {
+        self.early_hints = links;
+        self
+    }
+
+    /// Serve HTML files with a `<base href>` for this path, so that the
+    /// relative URLs of their assets resolve when the app is mounted under a
+    /// path prefix like `/app/`.
+    ///
+    /// The existing `<base>` tag in the head of the file is replaced, or one is
+    /// added right after the `<head>` tag. Files that aren't `text/html`, and
+    /// HTML files without a `<head>`, are served unchanged. `rust-embed` files
+    /// don't know their type, so use `with_content_type` or
+    /// `with_request_path_for_content_type` for them. The rewritten file
+    /// gets its own ETag, a hash of the rewritten contents, so the ETag differs
+    /// from the one of the embedded file. It's rewritten the first time it is
+    /// served with each base path and then cached. The precompressed versions
+    /// of the file can't be used for the rewritten file, so it's compressed on
+    /// the fly instead wherever the original would be sent compressed.
+    pub fn with_base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_path = Some(base_path.into());
+        self
+    }
+
+    /// Remove the UTF-8 byte order mark from the start of text files, like
+    /// HTML, CSS, JavaScript and JSON files.
+    ///
+    /// Some tools, especially on Windows, start UTF-8 files with a byte order
+    /// mark, which breaks JSON parsers and can break CSS. Like with
+    /// `with_base_path`, the stripped file gets its own ETag and is cached
+    /// after it's first served. Files without a byte order mark are served as
+    /// they are. Files without a type, like `rust-embed` files, are checked by
+    /// the extension of the request path. This is off by default.
+    pub fn with_strip_bom(mut self, enabled: bool) -> Self {
+        self.strip_bom = enabled;
+        self
+    }
+
+    /// Set the algorithm used to compute the ETag of the file.
+    ///
+    /// The default is to use the hash that the embed already computed. Other
+    /// algorithms hash the file the first time it is served, then cache the
+    /// result. This is useful if the ETags need to match another system, for
+    /// example a CDN that computes its own ETags.
+    pub fn with_etag_algorithm(mut self, algorithm: EtagAlgorithm) -> Self {
+        self.etag_algorithm = Some(algorithm);
+        self
+    }
+
+    /// Choose whether compressed responses get the same ETag as uncompressed
+    /// ones. See `EtagMode` for how each mode works with caches.
+    ///
+    /// The default is `EtagMode::Shared`, where all encodings of the file have
+    /// the same ETag.
+    pub fn with_etag_mode(mut self, mode: EtagMode) -> Self {
+        self.etag_mode = mode;
+        self
+    }
+
+    /// Set the body to send when the request uses a method other than `GET`
+    /// or `HEAD`, for example a JSON error for API clients.
+    ///
+    /// These requests get a `405 Method Not Allowed` response with an `Allow`
+    /// header listing `GET` and `HEAD`, which is empty by default.
+    pub fn with_method_not_allowed_body(
+        mut self,
+        body: impl Into<Bytes>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        self.method_not_allowed_body = Some((body.into(), mime_type.into()));
+        self
+    }
+
+    /// Set the `Content-Type` to send with the file, instead of guessing it.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Refuse to serve the file with `500 Internal Server Error` if its
+    /// contents clearly don't match its `Content-Type`, for example a file
+    /// declared as `text/html` that starts with the PNG signature.
+    ///
+    /// This protects against content type confusion, where a file is served
+    /// with a type that makes the browser treat it in a way it was never meant
+    /// to be. Only a small set of common formats are recognized from their
+    /// first bytes, and other types are always served. This is off by default.
+    pub fn with_content_type_guard(mut self, enabled: bool) -> Self {
+        self.content_type_guard = enabled;
+        self
+    }
+
+    /// Guess the `Content-Type` from the request path when the embedded file's
+    /// own path doesn't reveal it.
+    ///
+    /// This is useful when the files are embedded under keys without an
+    /// extension, for example content hashes, but the URLs they are served
+    /// under do have one. The request path is only used if the embed's guess
+    /// is missing or `application/octet-stream`.
+    pub fn with_request_path_for_content_type(mut self) -> Self {
+        self.content_type_from_request_path = true;
+        self
+    }
+
+    /// Only set these of the `MANAGED_HEADERS`, and leave the others to
+    /// middleware or to the handler.
+    ///
+    /// This avoids duplicate headers when another layer also sets them, for
+    /// example a middleware adding its own `Cache-Control`. Header names are
+    /// case insensitive. Conditional requests are still answered with the
+    /// ETag and modification time of the file even if those headers are left
+    /// out, so if another layer sets its own `ETag`, leave out `ETag` and
+    /// let that layer handle `If-None-Match` too.
+    ///
+    /// ```ignore
+    /// Embed::get(&path)
+    ///     .into_response()
+    ///     .with_managed_headers(&["ETag", "Content-Type"])
+    /// ```
+    pub fn with_managed_headers(mut self, headers: &[&str]) -> Self {
+        self.managed_headers = Some(headers.iter().map(|v| v.to_string()).collect());
+        self
+    }
+
+    /// Let the server in front of this one send the file, by sending an offload
+    /// header like `X-Accel-Redirect` for nginx or `X-Sendfile` instead of the
+    /// body.
+    ///
+    /// The value of the header is built from the request with `path`. The
+    /// response still carries all the caching headers like `ETag`, and
+    /// conditional requests are still answered with `304 Not Modified`.
+    ///
+    /// ```ignore
+    /// Embed::get(&path)
+    ///     .into_response()
+    ///     .with_offload_header("X-Accel-Redirect", |req| format!("/internal{}", req.path()))
+    /// ```
+    pub fn with_offload_header(
+        mut self,
+        name: impl Into<String>,
+        path: impl Fn(&HttpRequest) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.offload_header = Some((name.into(), Box::new(path)));
+        self
+    }
+
+    /// Call `f` with information about the response once it has been created,
+    /// for example to log it.
+    ///
+    /// ```ignore
+    /// Embed::get(&path)
+    ///     .into_response()
+    ///     .with_on_response(|info| log::info!("{} {} {:?}", info.path, info.status, info.encoding))
+    /// ```
+    pub fn with_on_response(mut self, f: impl FnOnce(&ServeInfo) + Send + 'static) -> Self {
+        self.on_response = Some(Box::new(f));
+        self
+    }
+
+    /// The file this response will serve, or `None` if it will be a 404.
+    pub fn file(&self) -> Option<&T> {
+        self.file.as_ref()
+    }
+
+    /// The name of the file this response will serve, if it is known.
+    ///
+    /// `rust-embed` files don't carry their name, so this is always `None` for them.
+    pub fn file_name(&self) -> Option<String> {
+        self.file.as_ref().and_then(|file| file.file_name())
+    }
+
+    /// The size of the uncompressed file in bytes.
+    pub fn content_length(&self) -> Option<usize> {
+        self.file.as_ref().map(|file| file.data().as_ref().len())
+    }
+
+    /// The ETag value that the response will use for the file.
+    pub fn etag(&self) -> Option<String> {
+        self.file.as_ref().map(|file| self.file_etag(file))
+    }
+
+    /// The mime type of the file, if one has been guessed.
+    pub fn mime_type(&self) -> Option<T::MimeType> {
+        self.file.as_ref().and_then(|file| file.mime_type())
+    }
 }
 
 /// A specialized version of `Into`, which can help you avoid specifying the type in `Into'.
@@ -215,3 +1761,27 @@ pub trait IntoResponse<T: EmbedRespondable> {
     /// A specialized version of `Into::into`.
     fn into_response(self) -> EmbedResponse<T>;
 }
+
+/// Turns a file that may be missing into a response, or a `NotFound` error
+/// if it is.
+///
+/// This makes it easy to use `?` in handlers that return a `Result`. Any error
+/// type that implements `From<NotFound>` works, as does `NotFound` itself or
+/// `actix_web::Error`.
+///
+/// ```ignore
+/// async fn handler(path: web::Path<String>) -> Result<EmbedResponse<EmbedableFileResponse>, NotFound> {
+///     let response = Embed::get(&path).try_into_response()?;
+///     Ok(response.with_cache_control("max-age=3600"))
+/// }
+/// ```
+pub trait TryIntoResponse<T: EmbedRespondable> {
+    /// Create the response, or a `NotFound` error if there is no file.
+    fn try_into_response(self) -> Result<EmbedResponse<T>, NotFound>;
+}
+
+impl<T: EmbedRespondable, F: IntoResponse<T>> TryIntoResponse<T> for Option<F> {
+    fn try_into_response(self) -> Result<EmbedResponse<T>, NotFound> {
+        self.map(IntoResponse::into_response).ok_or(NotFound)
+    }
+}
@@ -0,0 +1,281 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use base64::{engine::general_purpose::STANDARD_NO_PAD as Base64Encoder, Engine};
+use lazy_static::lazy_static;
+#[cfg(feature = "etag-sha1")]
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::embed::EmbedRespondable;
+
+/// Which algorithm to use for the ETag of the file.
+///
+/// Both the ETag header and the `If-None-Match` comparisons use the selected
+/// algorithm. Other than `Upstream`, these are computed the first time the
+/// file is served and then cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EtagAlgorithm {
+    /// Use the ETag that comes with the embedded file. This is a sha256 hash,
+    /// encoded with base64 for `rust-embed` and with base85 for
+    /// `rust-embed-for-web`. It's precomputed, so this is the cheapest option.
+    ///
+    /// Files whose ETag is blank, like `""`, get a weak ETag made from their
+    /// size and modification time instead, so a blank ETag is never sent or
+    /// compared.
+    #[default]
+    Upstream,
+    /// A base64 encoded sha256 hash of the file. This is the same as
+    /// `Upstream` for `rust-embed`.
+    Sha256Base64,
+    /// A hex encoded sha256 hash of the file.
+    ///
+    /// Both `rust-embed` and `rust-embed-for-web` have already computed the
+    /// sha256 hash, so this only changes how it is encoded.
+    Sha256Hex,
+    /// A weak ETag made from the size and crc32 checksum of the file.
+    ///
+    /// This is much cheaper to compute than a cryptographic hash, but it is a
+    /// weak validator: it's only used to decide whether the client's cached
+    /// copy can be reused, never for range requests.
+    Crc32,
+    /// A hex encoded sha1 hash of the file. Needs the `etag-sha1` feature.
+    #[cfg(feature = "etag-sha1")]
+    Sha1,
+    /// A hex encoded blake3 hash of the file. Needs the `etag-blake3` feature.
+    #[cfg(feature = "etag-blake3")]
+    Blake3,
+}
+
+/// Whether compressed responses get the same ETag as uncompressed ones.
+///
+/// With `Shared`, the gzip and the uncompressed version of a file have the
+/// same ETag. That's correct as long as caches respect `Vary: Accept-Encoding`,
+/// but a cache that doesn't may hand a compressed copy to a client that can't
+/// decompress it when revalidating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EtagMode {
+    /// Every encoding of the file has the same ETag.
+    #[default]
+    Shared,
+    /// Compressed responses get the ETag of the file with the encoding added,
+    /// like `"abc-gzip"` for `"abc"`. Caches then see every encoding as a
+    /// different version of the file, and a client revalidating its copy only
+    /// gets a `304 Not Modified` if it would get the same encoding again.
+    PerEncoding,
+    /// Only uncompressed responses have an ETag. Compressed responses can't
+    /// be revalidated with `If-None-Match`, only with `If-Modified-Since`,
+    /// which some CDN setups need to avoid mixing up the encodings.
+    NoneOnCompressed,
+}
+
+/// The ETag of the file, with the encoding of the response added to it.
+pub(crate) fn encoding_etag(etag: &str, encoding: &str) -> String {
+    match etag.strip_suffix('"') {
+        Some(etag) => format!("{etag}-{encoding}\""),
+        None => format!("{etag}-{encoding}"),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The sha256 hash of the file, reusing the one the embed computed if it can.
+pub(crate) fn sha256_hash<T: EmbedRespondable>(file: &T) -> [u8; 32] {
+    file.sha256_hash()
+        .unwrap_or_else(|| Sha256::digest(file.data().as_ref()).into())
+}
+
+// Putting the ETag into cache could potentially fail. That's okay if it does
+// happen, we'll just compute it again next time.
+#[allow(unused_must_use)]
+/// Computes the ETag of the file with the given algorithm, quoted and ready to
+/// be used as a header value.
+///
+/// Like the compressed data, the results are cached using the upstream ETag
+/// as the key, since that already uniquely identifies the file contents.
+/// `last_modified_timestamp` is the modification time the response is sent
+/// with, which the fallback for files without an ETag is made from.
+pub(crate) fn compute_etag<T: EmbedRespondable>(
+    algorithm: EtagAlgorithm,
+    file: &T,
+    last_modified_timestamp: Option<i64>,
+) -> String {
+    lazy_static! {
+        static ref CACHED_ETAGS: RwLock<HashMap<(EtagAlgorithm, String), String>> =
+            RwLock::new(HashMap::new());
+    }
+
+    let upstream = file.etag();
+    // Without an upstream ETag there is nothing to use as the cache key, so
+    // the ETag is computed every time.
+    if is_empty_etag(upstream.as_ref()) {
+        return match algorithm {
+            EtagAlgorithm::Upstream => fallback_etag(file, last_modified_timestamp),
+            algorithm => etag_for(algorithm, file),
+        };
+    }
+    if algorithm == EtagAlgorithm::Upstream {
+        return upstream.as_ref().to_string();
+    }
+    if !file.cacheable() {
+        return etag_for(algorithm, file);
+    }
+
+    let key = (algorithm, upstream.as_ref().to_string());
+    if let Some(etag) = CACHED_ETAGS
+        .read()
+        .ok()
+        .and_then(|cached| cached.get(&key).map(ToOwned::to_owned))
+    {
+        return etag;
+    }
+
+    let etag = etag_for(algorithm, file);
+    CACHED_ETAGS
+        .write()
+        .map(|mut cached| cached.insert(key, etag.clone()));
+    etag
+}
+
+fn etag_for<T: EmbedRespondable>(algorithm: EtagAlgorithm, file: &T) -> String {
+    match algorithm {
+        EtagAlgorithm::Upstream => file.etag().as_ref().to_string(),
+        EtagAlgorithm::Sha256Base64 => {
+            format!("\"{}\"", Base64Encoder.encode(sha256_hash(file)))
+        }
+        EtagAlgorithm::Sha256Hex => format!("\"{}\"", hex(&sha256_hash(file))),
+        EtagAlgorithm::Crc32 => {
+            let data = file.data();
+            let data = data.as_ref();
+            format!("W/\"{:x}-{:08x}\"", data.len(), crc32fast::hash(data))
+        }
+        #[cfg(feature = "etag-sha1")]
+        EtagAlgorithm::Sha1 => format!("\"{}\"", hex(&Sha1::digest(file.data().as_ref()))),
+        #[cfg(feature = "etag-blake3")]
+        EtagAlgorithm::Blake3 => format!("\"{}\"", blake3::hash(file.data().as_ref()).to_hex()),
+    }
+}
+
+/// Whether the ETag is missing, either completely empty, just whitespace, or
+/// just the quotes.
+pub(crate) fn is_empty_etag(etag: &str) -> bool {
+    etag.trim()
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .trim()
+        .is_empty()
+}
+
+/// The ETag to use for files that don't have one, like files from a custom
+/// `EmbedRespondable` that doesn't know the hash of the file. `rust-embed` and
+/// `rust-embed-for-web` always embed a hash, so their files never need this.
+///
+/// This is a weak ETag made from the size and the modification time, which is
+/// a weaker validator than a hash of the contents: two versions of a file with
+/// the same size and modification time get the same ETag. Files without a
+/// modification time use the crc32 checksum instead.
+fn fallback_etag<T: EmbedRespondable>(file: &T, last_modified_timestamp: Option<i64>) -> String {
+    match last_modified_timestamp {
+        Some(timestamp) => format!("W/\"{}-{}\"", file.data().as_ref().len(), timestamp),
+        None => etag_for(EtagAlgorithm::Crc32, file),
+    }
+}
+
+#[allow(unused_imports, dead_code)]
+mod test {
+    use super::{compute_etag, encoding_etag, is_empty_etag, EtagAlgorithm};
+    use crate::{EmbedRespondable, MemoryFile};
+
+    fn hello() -> MemoryFile {
+        MemoryFile::new(&b"hello world"[..], "hello")
+    }
+
+    #[test]
+    fn sha256_base64_etag() {
+        assert_eq!(
+            compute_etag(EtagAlgorithm::Sha256Base64, &hello(), None),
+            "\"uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek\""
+        );
+    }
+
+    #[test]
+    fn sha256_hex_etag() {
+        assert_eq!(
+            compute_etag(EtagAlgorithm::Sha256Hex, &hello(), None),
+            "\"b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9\""
+        );
+    }
+
+    #[test]
+    fn crc32_etag_is_weak() {
+        assert_eq!(
+            compute_etag(EtagAlgorithm::Crc32, &hello(), None),
+            "W/\"b-0d4a1185\""
+        );
+    }
+
+    #[cfg(feature = "etag-sha1")]
+    #[test]
+    fn sha1_etag() {
+        assert_eq!(
+            compute_etag(EtagAlgorithm::Sha1, &hello(), None),
+            "\"2aae6c35c94fcfb415dbe95f408b9ce91ee846ed\""
+        );
+    }
+
+    #[cfg(feature = "etag-blake3")]
+    #[test]
+    fn blake3_etag() {
+        assert_eq!(
+            compute_etag(EtagAlgorithm::Blake3, &hello(), None),
+            "\"d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24\""
+        );
+    }
+
+    #[test]
+    fn missing_etags_fall_back_to_size_and_mtime() {
+        let file =
+            MemoryFile::new(&b"hello world"[..], "").with_last_modified_timestamp(1445412480);
+        assert_eq!(
+            compute_etag(
+                EtagAlgorithm::Upstream,
+                &file,
+                file.last_modified_timestamp()
+            ),
+            "W/\"11-1445412480\""
+        );
+        let file = MemoryFile::new(&b"hello world"[..], "");
+        assert_eq!(
+            compute_etag(
+                EtagAlgorithm::Upstream,
+                &file,
+                file.last_modified_timestamp()
+            ),
+            "W/\"b-0d4a1185\""
+        );
+    }
+
+    #[test]
+    fn upstream_etag_is_unchanged() {
+        assert_eq!(
+            compute_etag(EtagAlgorithm::Upstream, &hello(), None),
+            "\"hello\""
+        );
+    }
+
+    #[test]
+    fn blank_etags_are_empty() {
+        for etag in ["", "\"\"", "W/\"\"", "  ", "\"  \"", " W/\" \" "] {
+            assert!(is_empty_etag(etag), "{etag:?}");
+        }
+        assert!(!is_empty_etag("\"a\""));
+        assert!(!is_empty_etag("W/\"a\""));
+    }
+
+    #[test]
+    fn encoding_is_added_inside_the_quotes() {
+        assert_eq!(encoding_etag("\"abc\"", "gzip"), "\"abc-gzip\"");
+        assert_eq!(encoding_etag("W/\"abc\"", "br"), "W/\"abc-br\"");
+    }
+}
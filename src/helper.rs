@@ -8,3 +8,9 @@ pub(crate) fn accepts_encoding(req: &HttpRequest, encoding: &str) -> bool {
         .map(|encodings| encodings.contains(&encoding))
         .unwrap_or(false)
 }
+
+/// Format a UNIX timestamp as an HTTP-date, like `Wed, 21 Oct 2015 07:28:00 GMT`.
+pub(crate) fn format_http_date(timestamp: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+}
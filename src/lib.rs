@@ -1,18 +1,41 @@
+mod body;
 mod helper;
 mod parse;
+mod rewrite;
 
+mod clock;
+pub use clock::{Clock, FixedClock, SystemClock};
 mod compress;
 pub use compress::*;
+mod defaults;
+pub use defaults::ResponderDefaults;
 mod embed;
+mod etag;
+pub use etag::{EtagAlgorithm, EtagMode};
+mod link;
+pub use link::LinkHeader;
+mod memory;
+pub use memory::{MemoryFile, MemoryFileResponse};
+mod mime;
+pub use mime::MimeRegistry;
+mod not_found;
+pub use not_found::{NotFound, NotFoundPage};
+mod path;
+pub use path::{serve_first, EmbedPath};
+mod serve_info;
+pub use serve_info::ServeInfo;
+mod sniff;
+mod validate;
+#[cfg(feature = "support-rust-embed")]
+pub use validate::validate_embed;
+pub use validate::{EmbedValidationError, EmbedValidator};
 
 #[cfg(feature = "support-rust-embed")]
 mod rust_embed;
-#[cfg(feature = "support-rust-embed")]
-pub use crate::rust_embed::*;
 
 #[cfg(feature = "support-rust-embed-for-web")]
 mod rust_embed_for_web;
 #[cfg(feature = "support-rust-embed-for-web")]
 pub use crate::rust_embed_for_web::*;
 
-pub use embed::{EmbedRespondable, EmbedResponse, IntoResponse};
+pub use embed::{EmbedRespondable, EmbedResponse, IntoResponse, TryIntoResponse, MANAGED_HEADERS};
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// A `Link` header entry, like the ones used to preload assets.
+///
+/// ```ignore
+/// LinkHeader::preload("/style.css").with_as("style")
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkHeader {
+    url: String,
+    rel: String,
+    as_type: Option<String>,
+    crossorigin: bool,
+}
+
+impl LinkHeader {
+    /// A link with the relation `rel`, for example `preconnect`.
+    pub fn new(url: impl Into<String>, rel: impl Into<String>) -> Self {
+        LinkHeader {
+            url: url.into(),
+            rel: rel.into(),
+            as_type: None,
+            crossorigin: false,
+        }
+    }
+
+    /// A link that tells the browser to start fetching `url` right away.
+    pub fn preload(url: impl Into<String>) -> Self {
+        LinkHeader::new(url, "preload")
+    }
+
+    /// Set what kind of resource the link is for, like `style`, `script`,
+    /// `font` or `image`. Browsers need this to preload the resource.
+    pub fn with_as(mut self, as_type: impl Into<String>) -> Self {
+        self.as_type = Some(as_type.into());
+        self
+    }
+
+    /// Fetch the resource with CORS. This is required for preloading fonts,
+    /// even from the same origin.
+    pub fn with_crossorigin(mut self) -> Self {
+        self.crossorigin = true;
+        self
+    }
+}
+
+impl fmt::Display for LinkHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>; rel={}", self.url, self.rel)?;
+        if let Some(as_type) = &self.as_type {
+            write!(f, "; as={as_type}")?;
+        }
+        if self.crossorigin {
+            write!(f, "; crossorigin")?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(unused_imports)]
+mod test {
+    use super::LinkHeader;
+
+    #[test]
+    fn formats_link_headers() {
+        assert_eq!(
+            LinkHeader::preload("/style.css")
+                .with_as("style")
+                .to_string(),
+            "</style.css>; rel=preload; as=style"
+        );
+        assert_eq!(
+            LinkHeader::preload("/font.woff2")
+                .with_as("font")
+                .with_crossorigin()
+                .to_string(),
+            "</font.woff2>; rel=preload; as=font; crossorigin"
+        );
+        assert_eq!(
+            LinkHeader::new("https://cdn.example.com", "preconnect").to_string(),
+            "<https://cdn.example.com>; rel=preconnect"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::borrow::Cow;
+
+use actix_web::web::Bytes;
+use base64::{engine::general_purpose::STANDARD_NO_PAD as Base64Encoder, Engine};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    clock::{Clock, SystemClock},
+    embed::{EmbedRespondable, EmbedResponse, IntoResponse},
+    helper::format_http_date,
+};
+
+/// A file that is held in memory instead of being embedded, for example a file
+/// generated at runtime.
+///
+/// Serving a `MemoryFile` goes through the same conditional request and
+/// compression handling as embedded files do. Since there is nothing to compute
+/// these from, you have to supply the ETag yourself, and optionally the last
+/// modification time and mime type. The data is kept as `Bytes`, so serving
+/// the file shares it with the response instead of copying it.
+///
+/// Unlike embedded files, nothing computed for a memory file is cached: it's
+/// compressed again every time it's sent compressed. A file that is
+/// regenerated would otherwise leave every old version in the caches for the
+/// life of the process. If compressing it every time is too costly, send it
+/// with `Compress::Never`.
+///
+/// ```ignore
+/// let manifest = serde_json::to_vec(&manifest)?;
+/// MemoryFile::new(manifest, "manifest-v3")
+///     .with_mime_type("application/json")
+///     .into_response()
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryFile {
+    data: Bytes,
+    etag: String,
+    last_modified_timestamp: Option<i64>,
+    mime_type: Option<String>,
+}
+
+/// The response type for files held in memory.
+pub type MemoryFileResponse = EmbedResponse<MemoryFile>;
+
+impl MemoryFile {
+    /// Create a file with this data and ETag.
+    ///
+    /// The ETag is quoted for you if it isn't quoted already. Make sure it
+    /// changes whenever the data changes, otherwise clients may keep using a
+    /// stale copy of the file.
+    pub fn new(data: impl Into<Cow<'static, [u8]>>, etag: impl Into<String>) -> Self {
+        let etag = etag.into();
+        let etag = if etag.starts_with('"') || etag.starts_with("W/\"") {
+            etag
+        } else {
+            format!("\"{etag}\"")
+        };
+        MemoryFile {
+            data: cow_to_bytes(data.into()),
+            etag,
+            last_modified_timestamp: None,
+            mime_type: None,
+        }
+    }
+
+    /// Create a file with this data, working out everything else from it.
+    ///
+    /// The ETag is a sha256 hash of the data, the mime type is guessed from
+    /// the extension of `path_hint`, and the file is last modified now. If the
+    /// data is generated for every request, `If-Modified-Since` will never
+    /// match, but `If-None-Match` still does as long as the data is the same.
+    ///
+    /// ```ignore
+    /// let manifest = serde_json::to_vec(&manifest)?;
+    /// MemoryFile::from_bytes(manifest, "manifest.json").into_response()
+    /// ```
+    pub fn from_bytes(bytes: impl Into<Cow<'static, [u8]>>, path_hint: &str) -> Self {
+        let bytes = bytes.into();
+        let etag = Base64Encoder.encode(Sha256::digest(&bytes));
+        let file = MemoryFile::new(bytes, etag).with_last_modified_timestamp(SystemClock.now());
+        match mime_guess::from_path(path_hint).first_raw() {
+            Some(mime_type) => file.with_mime_type(mime_type),
+            None => file,
+        }
+    }
+
+    /// Set the UNIX timestamp of when the file was last modified.
+    pub fn with_last_modified_timestamp(mut self, timestamp: i64) -> Self {
+        self.last_modified_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Set the mime type of the file.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// `Bytes` has no conversion from a `Cow`, and going through a `Vec` would copy
+/// borrowed data.
+fn cow_to_bytes(data: Cow<'static, [u8]>) -> Bytes {
+    match data {
+        Cow::Borrowed(data) => Bytes::from_static(data),
+        Cow::Owned(data) => Bytes::from(data),
+    }
+}
+
+impl From<MemoryFile> for EmbedResponse<MemoryFile> {
+    fn from(file: MemoryFile) -> Self {
+        EmbedResponse::new(Some(file))
+    }
+}
+
+impl From<Option<MemoryFile>> for EmbedResponse<MemoryFile> {
+    fn from(file: Option<MemoryFile>) -> Self {
+        EmbedResponse::new(file)
+    }
+}
+
+impl IntoResponse<MemoryFile> for MemoryFile {
+    fn into_response(self) -> EmbedResponse<MemoryFile> {
+        self.into()
+    }
+}
+
+impl IntoResponse<MemoryFile> for Option<MemoryFile> {
+    fn into_response(self) -> EmbedResponse<MemoryFile> {
+        self.into()
+    }
+}
+
+impl EmbedRespondable for MemoryFile {
+    type Data = Bytes;
+    type DataGzip = Vec<u8>;
+    type DataBr = Vec<u8>;
+    type ETag = String;
+    type LastModified = String;
+    type MimeType = String;
+
+    fn data(&self) -> Self::Data {
+        self.data.clone()
+    }
+
+    fn data_gzip(&self) -> Option<Self::DataGzip> {
+        None
+    }
+
+    fn data_br(&self) -> Option<Self::DataBr> {
+        None
+    }
+
+    fn last_modified(&self) -> Option<Self::LastModified> {
+        self.last_modified_timestamp.and_then(format_http_date)
+    }
+
+    fn last_modified_timestamp(&self) -> Option<i64> {
+        self.last_modified_timestamp
+    }
+
+    fn etag(&self) -> Self::ETag {
+        self.etag.clone()
+    }
+
+    fn mime_type(&self) -> Option<Self::MimeType> {
+        self.mime_type.clone()
+    }
+
+    // Memory files are usually generated at runtime, and every new version
+    // would stay in the caches forever.
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,99 @@
+use std::{collections::HashMap, path::Path};
+
+use actix_web::{web, HttpRequest};
+
+/// Custom mappings from file extensions to mime types, for all the responses
+/// in an app.
+///
+/// Register this as app data, and every `EmbedResponse` will use these mime
+/// types for files with a matching extension, instead of the one that the
+/// embed guessed. A mime type set on the response with
+/// `EmbedResponse::with_content_type` still takes priority.
+///
+/// ```ignore
+/// App::new()
+///     .app_data(
+///         MimeRegistry::new()
+///             .with_mapping("wasm", "application/wasm")
+///             .with_mapping("mjs", "text/javascript"),
+///     )
+///     .service(serve_assets)
+/// ```
+///
+/// Both `.app_data(registry)` and `.app_data(web::Data::new(registry))` work.
+#[derive(Debug, Clone, Default)]
+pub struct MimeRegistry {
+    mappings: HashMap<String, String>,
+}
+
+impl MimeRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Use `mime_type` for files with the extension `extension`. The extension
+    /// is case insensitive, and may be given with or without the leading dot.
+    ///
+    /// Compound extensions like `tar.gz` work too. The longest extension that
+    /// has a mapping wins, so with mappings for both `tar.gz` and `gz`, the
+    /// file `backup.tar.gz` gets the one for `tar.gz`.
+    pub fn with_mapping(mut self, extension: &str, mime_type: impl Into<String>) -> Self {
+        self.mappings
+            .insert(normalize_extension(extension), mime_type.into());
+        self
+    }
+
+    /// The mime type registered for the extension of `path`, if any.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        // A leading dot is part of the name of a dotfile, not an extension.
+        let file_name = Path::new(path)
+            .file_name()?
+            .to_str()?
+            .trim_start_matches('.');
+        file_name
+            .match_indices('.')
+            .find_map(|(index, _)| {
+                self.mappings
+                    .get(&normalize_extension(&file_name[index + 1..]))
+            })
+            .map(|mime_type| mime_type.as_str())
+    }
+
+    /// Get the registry registered for the app handling this request, if any.
+    pub fn from_app_data(req: &HttpRequest) -> Option<&Self> {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|v| v.get_ref()))
+    }
+}
+
+fn normalize_extension(extension: &str) -> String {
+    extension.trim_start_matches('.').to_ascii_lowercase()
+}
+
+#[allow(unused_imports)]
+mod test {
+    use super::MimeRegistry;
+
+    #[test]
+    fn extensions_are_case_insensitive() {
+        let registry = MimeRegistry::new().with_mapping(".WASM", "application/wasm");
+        assert_eq!(registry.get("module.wasm"), Some("application/wasm"));
+        assert_eq!(registry.get("dir/Module.Wasm"), Some("application/wasm"));
+        assert_eq!(registry.get("module.js"), None);
+        assert_eq!(registry.get("wasm"), None);
+    }
+
+    #[test]
+    fn longest_compound_extension_wins() {
+        let registry = MimeRegistry::new()
+            .with_mapping("gz", "application/gzip")
+            .with_mapping("tar.gz", "application/x-gtar");
+        assert_eq!(registry.get("backup.tar.gz"), Some("application/x-gtar"));
+        assert_eq!(
+            registry.get("dir.v2/backup.TAR.GZ"),
+            Some("application/x-gtar")
+        );
+        assert_eq!(registry.get("notes.txt.gz"), Some("application/gzip"));
+        assert_eq!(registry.get(".gz"), None);
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+use actix_web::{
+    http::{header::CACHE_CONTROL, StatusCode},
+    HttpRequest, HttpResponse, ResponseError,
+};
+
+use crate::embed::{EmbedRespondable, EmbedResponse};
+
+/// Serves an embedded `404 Not Found` page, picking an HTML or a JSON page
+/// based on the `Accept` header of the request.
+///
+/// Browsers get `404.html`, while API clients that ask for JSON get
+/// `404.json`. The page is served with a `404` status and `Cache-Control:
+/// no-store`, and otherwise goes through the same handling as any other file,
+/// so it gets the right `Content-Type` and can be compressed.
+///
+/// ```ignore
+/// async fn handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+///     match Embed::get(req.path()) {
+///         Some(file) => file.into_response(),
+///         None => NotFoundPage::new().resolve(&req, Embed::get),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NotFoundPage {
+    html: String,
+    json: String,
+}
+
+impl Default for NotFoundPage {
+    fn default() -> Self {
+        NotFoundPage {
+            html: "404.html".to_string(),
+            json: "404.json".to_string(),
+        }
+    }
+}
+
+impl NotFoundPage {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the path of the HTML page in the embed. The default is `404.html`.
+    pub fn with_html(mut self, path: impl Into<String>) -> Self {
+        self.html = path.into();
+        self
+    }
+
+    /// Set the path of the JSON page in the embed. The default is `404.json`.
+    pub fn with_json(mut self, path: impl Into<String>) -> Self {
+        self.json = path.into();
+        self
+    }
+
+    /// Look up the right page for this request with `get` and create a
+    /// response for it.
+    ///
+    /// `get` is the getter of your embed, like `Embed::get`. If the page
+    /// doesn't exist in the embed, the response is an empty 404.
+    pub fn resolve<T, F, G>(&self, req: &HttpRequest, get: G) -> EmbedResponse<T>
+    where
+        T: EmbedRespondable,
+        G: Fn(&str) -> Option<F>,
+        Option<F>: Into<EmbedResponse<T>>,
+    {
+        let path = if prefers_json(req) {
+            &self.json
+        } else {
+            &self.html
+        };
+        get(path)
+            .into()
+            .with_status(StatusCode::NOT_FOUND)
+            .with_cache_control("no-store")
+    }
+}
+
+/// The error for a file that isn't in the embed.
+///
+/// This is what `try_into_response` returns for a missing file. As a
+/// `ResponseError`, it's sent as an empty `404 Not Found` with
+/// `Cache-Control: no-store`, so a handler can return it directly or convert
+/// it into its own error type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NotFound;
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("file not found")
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+impl ResponseError for NotFound {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::NOT_FOUND
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::NotFound()
+            .insert_header((CACHE_CONTROL, "no-store"))
+            .finish()
+    }
+}
+
+/// Whether the client would rather have JSON than HTML. When both are equally
+/// acceptable, like with `*/*` or no `Accept` header at all, HTML wins.
+fn prefers_json(req: &HttpRequest) -> bool {
+    let Some(accept) = req.headers().get("Accept").and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    let mut json = 0.0;
+    let mut html = 0.0;
+    for item in accept.split(',') {
+        let mut parts = item.split(';');
+        let mime_type = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        let quality = parts
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .and_then(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        match mime_type.as_str() {
+            "application/json" | "application/*" => json = f32::max(json, quality),
+            "text/html" | "text/*" => html = f32::max(html, quality),
+            "*/*" => {
+                json = f32::max(json, quality);
+                html = f32::max(html, quality);
+            }
+            mime_type if mime_type.ends_with("+json") => json = f32::max(json, quality),
+            _ => {}
+        }
+    }
+    json > html
+}
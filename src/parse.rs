@@ -2,8 +2,36 @@ use actix_web::http::header::HeaderValue;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+/// The ETags in an `If-None-Match` header, and `*` if it's there.
 pub(crate) fn parse_if_none_match_value(value: &HeaderValue) -> Option<Vec<&str>> {
-    parse_comma_seperated_list(value, parse_single_etag_value)
+    parse_comma_seperated_list(value, parse_single_if_none_match_item)
+}
+
+/// Whether the `If-None-Match` header has the ETag in it, using the weak
+/// comparison. `*` matches any file. `None` means the header couldn't be read
+/// at all.
+///
+/// Almost every conditional request has a single ETag, the one the client got
+/// with its cached copy, so that case is compared directly instead of going
+/// through the list.
+pub(crate) fn if_none_match_matches(value: &HeaderValue, etag: &str) -> Option<bool> {
+    let etag = etag.trim_start_matches("W/");
+    let item = value.to_str().ok()?;
+    if !item.contains(',') {
+        let item = item.trim_matches(|c: char| c.is_ascii_whitespace());
+        return Some(item == "*" || parse_single_etag_value(item) == Some(etag));
+    }
+    parse_if_none_match_value(value)
+        .map(|etags| etags.iter().any(|item| *item == "*" || *item == etag))
+}
+
+/// Whether the `If-None-Match` header is `*`, which matches any file, even
+/// one sent without an ETag.
+pub(crate) fn is_if_none_match_wildcard(value: &HeaderValue) -> bool {
+    value
+        .to_str()
+        .map(|v| v.trim_matches(|c: char| c.is_ascii_whitespace()) == "*")
+        .unwrap_or(false)
 }
 
 pub(crate) fn parse_accept_encoding_value(value: &HeaderValue) -> Option<Vec<&str>> {
@@ -14,20 +42,31 @@ fn parse_comma_seperated_list(
     value: &HeaderValue,
     parse_item: fn(&str) -> Option<&str>,
 ) -> Option<Vec<&str>> {
-    value
-        .to_str()
-        .ok()
-        .map(|v| v.split(',').filter_map(parse_item).collect::<Vec<&str>>())
+    value.to_str().ok().map(|v| {
+        v.split(',')
+            .map(|item| item.trim_matches(|c: char| c.is_ascii_whitespace()))
+            .filter_map(parse_item)
+            .collect::<Vec<&str>>()
+    })
 }
 
+/// The quoted ETag, without the `W/` prefix if it's a weak one.
+///
+/// This runs for every conditional request, so it's a few string checks rather
+/// than a regex.
 fn parse_single_etag_value(value: &str) -> Option<&str> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r#"^ *(W/)?(?P<value>"[^"]+") *$"#).unwrap();
-    }
+    let value = value.trim_matches(' ');
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    (!inner.is_empty() && !inner.contains('"')).then_some(value)
+}
 
-    RE.captures(value)
-        .and_then(|v| v.name("value"))
-        .map(|v| v.as_str())
+/// An ETag like `parse_single_etag_value`, or `*`.
+fn parse_single_if_none_match_item(value: &str) -> Option<&str> {
+    if value == "*" {
+        return Some(value);
+    }
+    parse_single_etag_value(value)
 }
 
 fn parse_single_encoding_value(value: &str) -> Option<&str> {
@@ -39,3 +78,258 @@ fn parse_single_encoding_value(value: &str) -> Option<&str> {
         .and_then(|v| v.name("value"))
         .map(|v| v.as_str())
 }
+
+/// Parse an HTTP-date, like the ones in `If-Unmodified-Since`, into a UNIX
+/// timestamp.
+///
+/// HTTP-dates are always in GMT. A `+0000` offset is the same time, and it's
+/// what chrono's rfc2822 dates use, so clients sending back a `Last-Modified`
+/// formatted that way, like the ones `rust-embed-for-web` files come with,
+/// still match. This leniency is only for the headers of the request, the
+/// dates this crate formats itself always end in `GMT`. Dates with any other offset, even ones that
+/// are otherwise valid rfc2822 dates, give `None` so that the header gets
+/// ignored instead of being compared against the wrong time.
+pub(crate) fn parse_http_date(value: &HeaderValue) -> Option<i64> {
+    let value = value.to_str().ok()?.trim();
+    if !value.ends_with(" GMT") && !value.ends_with(" +0000") {
+        return None;
+    }
+    chrono::DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&chrono::Utc).timestamp())
+}
+
+/// Checks that every entry of an `If-None-Match` header is either an ETag or `*`.
+pub(crate) fn is_valid_if_none_match_value(value: &HeaderValue) -> bool {
+    value
+        .to_str()
+        .map(|v| {
+            v.split(',')
+                .map(|item| item.trim_matches(|c: char| c.is_ascii_whitespace()))
+                .all(|item| item == "*" || parse_single_etag_value(item).is_some())
+        })
+        .unwrap_or(false)
+}
+
+/// Checks that a `Range` header can be parsed. Ranges with units other than
+/// `bytes` are valid, they just get ignored.
+pub(crate) fn is_valid_range_value(value: &HeaderValue) -> bool {
+    match value.to_str().ok().and_then(|v| v.split_once('=')) {
+        Some((unit, _)) if !unit.trim().eq_ignore_ascii_case("bytes") => true,
+        Some(_) => parse_range_value(value).is_some(),
+        None => false,
+    }
+}
+
+/// A single range from a `Range` header, before it has been checked against
+/// the length of the file.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RangeSpec {
+    /// `start-end` or `start-`, where the end is inclusive.
+    FromTo(u64, Option<u64>),
+    /// `-length`, the last `length` bytes of the file.
+    Suffix(u64),
+}
+
+impl RangeSpec {
+    /// Get the (inclusive) start and end of this range for a file of length
+    /// `len`, or `None` if the range is not satisfiable.
+    pub(crate) fn resolve(&self, len: u64) -> Option<(u64, u64)> {
+        match *self {
+            RangeSpec::FromTo(start, end) => {
+                if start >= len {
+                    return None;
+                }
+                let end = end.map(|end| end.min(len - 1)).unwrap_or(len - 1);
+                Some((start, end))
+            }
+            RangeSpec::Suffix(0) => None,
+            RangeSpec::Suffix(suffix) => {
+                if len == 0 {
+                    return None;
+                }
+                Some((len.saturating_sub(suffix), len - 1))
+            }
+        }
+    }
+}
+
+/// Parse a `Range` header.
+///
+/// Only the `bytes` unit is supported. Any other unit, or a header that fails
+/// to parse, gives `None` which means the header should be ignored.
+pub(crate) fn parse_range_value(value: &HeaderValue) -> Option<Vec<RangeSpec>> {
+    let (unit, ranges) = value.to_str().ok()?.split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    ranges
+        .split(',')
+        .map(parse_single_range_value)
+        .collect::<Option<Vec<RangeSpec>>>()
+        .filter(|ranges| !ranges.is_empty())
+}
+
+fn parse_single_range_value(value: &str) -> Option<RangeSpec> {
+    let (start, end) = value.trim().split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        return end.parse().ok().map(RangeSpec::Suffix);
+    }
+    let start: u64 = start.parse().ok()?;
+    if end.is_empty() {
+        return Some(RangeSpec::FromTo(start, None));
+    }
+    let end: u64 = end.parse().ok()?;
+    if end < start {
+        return None;
+    }
+    Some(RangeSpec::FromTo(start, Some(end)))
+}
+
+#[allow(unused_imports)]
+mod test {
+    use super::{
+        if_none_match_matches, is_if_none_match_wildcard, is_valid_if_none_match_value,
+        is_valid_range_value, parse_http_date, parse_if_none_match_value, parse_range_value,
+        RangeSpec,
+    };
+    use actix_web::http::header::HeaderValue;
+
+    #[test]
+    fn parses_byte_ranges() {
+        let ranges = parse_range_value(&HeaderValue::from_static("bytes=0-99, 200-, -50"));
+        assert_eq!(
+            ranges,
+            Some(vec![
+                RangeSpec::FromTo(0, Some(99)),
+                RangeSpec::FromTo(200, None),
+                RangeSpec::Suffix(50),
+            ])
+        );
+    }
+
+    #[test]
+    fn ignores_other_units() {
+        assert_eq!(
+            parse_range_value(&HeaderValue::from_static("items=0-10")),
+            None
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_ranges() {
+        assert_eq!(parse_range_value(&HeaderValue::from_static("bytes=")), None);
+        assert_eq!(
+            parse_range_value(&HeaderValue::from_static("bytes=a-b")),
+            None
+        );
+        assert_eq!(
+            parse_range_value(&HeaderValue::from_static("bytes=10-5")),
+            None
+        );
+        assert_eq!(parse_range_value(&HeaderValue::from_static("0-10")), None);
+    }
+
+    #[test]
+    fn resolves_ranges_against_length() {
+        assert_eq!(RangeSpec::FromTo(0, Some(99)).resolve(50), Some((0, 49)));
+        assert_eq!(RangeSpec::FromTo(10, None).resolve(50), Some((10, 49)));
+        assert_eq!(RangeSpec::FromTo(50, None).resolve(50), None);
+        assert_eq!(RangeSpec::Suffix(10).resolve(50), Some((40, 49)));
+        assert_eq!(RangeSpec::Suffix(100).resolve(50), Some((0, 49)));
+        assert_eq!(RangeSpec::Suffix(0).resolve(50), None);
+    }
+
+    #[test]
+    fn parses_gmt_http_dates() {
+        assert_eq!(
+            parse_http_date(&HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(1445412480)
+        );
+    }
+
+    #[test]
+    fn accepts_zero_utc_offsets() {
+        assert_eq!(
+            parse_http_date(&HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 +0000")),
+            Some(1445412480)
+        );
+    }
+
+    #[test]
+    fn ignores_dates_that_are_not_gmt() {
+        assert_eq!(
+            parse_http_date(&HeaderValue::from_static("Wed, 21 Oct 2015 09:28:00 +0200")),
+            None
+        );
+        assert_eq!(
+            parse_http_date(&HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 -0500")),
+            None
+        );
+        assert_eq!(
+            parse_http_date(&HeaderValue::from_static("not a date GMT")),
+            None
+        );
+    }
+
+    #[test]
+    fn matches_if_none_match() {
+        let matches = |value, etag| if_none_match_matches(&HeaderValue::from_static(value), etag);
+        assert_eq!(matches("\"a\"", "\"a\""), Some(true));
+        assert_eq!(matches(" W/\"a\" ", "\"a\""), Some(true));
+        assert_eq!(matches("\"a\"", "W/\"a\""), Some(true));
+        assert_eq!(matches("\"b\"", "\"a\""), Some(false));
+        assert_eq!(matches("\"b\", W/\"a\"", "\"a\""), Some(true));
+        assert_eq!(matches("\"b\", \"c\"", "\"a\""), Some(false));
+        assert_eq!(matches("a", "a"), Some(false));
+        assert_eq!(matches("\"\"", "\"\""), Some(false));
+        assert_eq!(matches("*", "\"a\""), Some(true));
+        assert_eq!(matches(" * ", "W/\"a\""), Some(true));
+        assert_eq!(matches("\"b\", *", "\"a\""), Some(true));
+        assert_eq!(
+            parse_if_none_match_value(&HeaderValue::from_static("\"a\", W/\"b\", c")),
+            Some(vec!["\"a\"", "\"b\""])
+        );
+    }
+
+    #[test]
+    fn detects_if_none_match_wildcard() {
+        assert!(is_if_none_match_wildcard(&HeaderValue::from_static("*")));
+        assert!(is_if_none_match_wildcard(&HeaderValue::from_static(" * ")));
+        assert!(!is_if_none_match_wildcard(&HeaderValue::from_static(
+            "\"*\""
+        )));
+    }
+
+    #[test]
+    fn validates_if_none_match() {
+        assert!(is_valid_if_none_match_value(&HeaderValue::from_static(
+            "\"a\", W/\"b\""
+        )));
+        assert!(is_valid_if_none_match_value(&HeaderValue::from_static("*")));
+        assert!(!is_valid_if_none_match_value(&HeaderValue::from_static(
+            "\"a\", b"
+        )));
+    }
+
+    #[test]
+    fn validates_ranges() {
+        assert!(is_valid_range_value(&HeaderValue::from_static(
+            "bytes=0-10"
+        )));
+        assert!(is_valid_range_value(&HeaderValue::from_static("items=a-b")));
+        assert!(!is_valid_range_value(&HeaderValue::from_static(
+            "bytes=a-b"
+        )));
+        assert!(!is_valid_range_value(&HeaderValue::from_static("0-10")));
+    }
+
+    #[test]
+    fn trims_whitespace_around_etags() {
+        assert_eq!(
+            parse_if_none_match_value(&HeaderValue::from_static("\"a\" ,\t\"b\"\t, W/\"c\"  ")),
+            Some(vec!["\"a\"", "\"b\"", "\"c\""])
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::borrow::Cow;
+
+use actix_web::HttpRequest;
+use percent_encoding::percent_decode_str;
+
+use crate::embed::{EmbedRespondable, EmbedResponse};
+
+/// Serves embedded files based on the path of the request.
+///
+/// This lets you use a single handler to serve a whole embed, without having
+/// to extract the path of the file yourself. If you mount the handler somewhere
+/// other than the root of your app, set the mount prefix with
+/// `.with_prefix()` so it gets removed before the file is looked up.
+///
+/// ```ignore
+/// async fn handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+///     EmbedPath::new().with_prefix("/static").resolve(&req, Embed::get)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmbedPath {
+    prefix: String,
+    clean_urls: bool,
+    trailing_slash_redirect: bool,
+}
+
+impl EmbedPath {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the path this handler is mounted at, for example `/static`.
+    ///
+    /// Leading and trailing slashes don't matter: `/static`, `/static/` and
+    /// `static` are all treated the same way.
+    pub fn with_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.prefix = prefix.as_ref().trim_matches('/').to_string();
+        self
+    }
+
+    /// Serve `about.html` for `/about`, and `docs/index.html` for `/docs`, like
+    /// most static site hosts do.
+    ///
+    /// With this enabled, `path` is tried first, then `path.html`, then
+    /// `path/index.html`. The first one that exists is served, with the same
+    /// conditional request and compression handling as any other file. This
+    /// is off by default.
+    pub fn with_clean_urls(mut self, enabled: bool) -> Self {
+        self.clean_urls = enabled;
+        self
+    }
+
+    /// Redirect `/docs` to `/docs/` when there is a `docs/index.html`, and
+    /// serve `docs/index.html` for `/docs/`, like nginx and Caddy do.
+    ///
+    /// The redirect is a `301 Moved Permanently`, and it keeps the query
+    /// string. Relative links in the index page then resolve against the
+    /// directory rather than its parent. With `with_clean_urls` also enabled,
+    /// `docs.html` is still served for `/docs` if it exists. This is off by
+    /// default.
+    pub fn with_trailing_slash_redirect(mut self, enabled: bool) -> Self {
+        self.trailing_slash_redirect = enabled;
+        self
+    }
+
+    /// Remove the mount prefix from a request path, returning the path of the
+    /// embedded file relative to the root of the embed.
+    ///
+    /// Returns `None` if the path is not under the mount prefix.
+    pub fn strip_prefix<'a>(&self, path: &'a str) -> Option<&'a str> {
+        let path = path.trim_start_matches('/');
+        if self.prefix.is_empty() {
+            return Some(path);
+        }
+        let rest = path.strip_prefix(self.prefix.as_str())?;
+        if rest.is_empty() {
+            Some(rest)
+        } else {
+            // Make sure we don't match `/staticfoo` with the prefix `/static`.
+            rest.strip_prefix('/')
+        }
+    }
+
+    /// Look up the file for this request with `get` and create a response for it.
+    ///
+    /// `get` is the getter of your embed, like `Embed::get`. If the request is
+    /// for the root of the mount, `index.html` is served. If the path is not
+    /// under the mount prefix or the file doesn't exist, the response is a 404.
+    /// The path is percent-decoded before it's looked up.
+    /// See `with_clean_urls` for serving HTML files without their extension,
+    /// and `with_trailing_slash_redirect` for serving directories.
+    pub fn resolve<T, F, G>(&self, req: &HttpRequest, get: G) -> EmbedResponse<T>
+    where
+        T: EmbedRespondable,
+        G: Fn(&str) -> Option<F>,
+        Option<F>: Into<EmbedResponse<T>>,
+    {
+        let Some(path) = self.strip_prefix(req.path()).and_then(decode_path) else {
+            return None::<F>.into();
+        };
+        let path = path.as_ref();
+        if path.is_empty() {
+            return get("index.html").into();
+        }
+        let file = get(path);
+        if file.is_some() || !(self.clean_urls || self.trailing_slash_redirect) {
+            return file.into();
+        }
+
+        let path_without_slash = path.trim_end_matches('/');
+        if self.clean_urls {
+            if let Some(file) = get(&format!("{path_without_slash}.html")) {
+                return Some(file).into();
+            }
+        }
+        let index = get(&format!("{path_without_slash}/index.html"));
+        if self.trailing_slash_redirect && index.is_some() && !path.ends_with('/') {
+            // Several leading slashes would make this a protocol-relative URL,
+            // redirecting `//example.com/docs` to another host.
+            let mut location = format!("/{}/", req.path().trim_start_matches('/'));
+            if !req.query_string().is_empty() {
+                location.push('?');
+                location.push_str(req.query_string());
+            }
+            let mut resp: EmbedResponse<T> = None::<F>.into();
+            resp.redirect = Some(location);
+            return resp;
+        }
+        index.into()
+    }
+}
+
+/// Percent-decode a request path, so that `/my%20file.css` finds `my file.css`.
+///
+/// Paths that aren't valid UTF-8 once decoded, or that have a `..` segment,
+/// give `None`. Embeds in debug builds read files from the disk, so a `..`
+/// could reach files outside of the embedded folder.
+fn decode_path(path: &str) -> Option<Cow<'_, str>> {
+    let path = percent_decode_str(path).decode_utf8().ok()?;
+    if path.split('/').any(|segment| segment == "..") {
+        return None;
+    }
+    Some(path)
+}
+
+/// Serve the first of `paths` that exists in the embed, or a 404 if none do.
+///
+/// `get` is the getter of your embed, like `Embed::get`. This is useful for
+/// fallbacks, like serving a localized page if there is one and the default
+/// page otherwise. Leading slashes in the paths are ignored.
+///
+/// ```ignore
+/// async fn handler() -> EmbedResponse<EmbedableFileResponse> {
+///     serve_first(Embed::get, &["en/page.html", "page.html"])
+/// }
+/// ```
+pub fn serve_first<T, F, G>(get: G, paths: &[&str]) -> EmbedResponse<T>
+where
+    T: EmbedRespondable,
+    G: Fn(&str) -> Option<F>,
+    Option<F>: Into<EmbedResponse<T>>,
+{
+    paths
+        .iter()
+        .find_map(|path| get(path.trim_start_matches('/')))
+        .into()
+}
+
+#[allow(unused_imports)]
+mod test {
+    use super::decode_path;
+    use crate::EmbedPath;
+    use std::borrow::Cow;
+
+    #[test]
+    fn root_mount_keeps_path() {
+        let path = EmbedPath::new();
+        assert_eq!(path.strip_prefix("/style.css"), Some("style.css"));
+        assert_eq!(path.strip_prefix("/"), Some(""));
+    }
+
+    #[test]
+    fn prefix_is_stripped() {
+        let path = EmbedPath::new().with_prefix("/assets");
+        assert_eq!(path.strip_prefix("/assets/style.css"), Some("style.css"));
+        assert_eq!(path.strip_prefix("/assets/"), Some(""));
+        assert_eq!(path.strip_prefix("/assets"), Some(""));
+    }
+
+    #[test]
+    fn prefix_slashes_are_normalized() {
+        let path = EmbedPath::new().with_prefix("assets/");
+        assert_eq!(path.strip_prefix("/assets/style.css"), Some("style.css"));
+    }
+
+    #[test]
+    fn paths_are_percent_decoded() {
+        assert_eq!(decode_path("my%20file.css").as_deref(), Some("my file.css"));
+        assert_eq!(
+            decode_path("caf%C3%A9/men%C3%BC.html").as_deref(),
+            Some("café/menü.html")
+        );
+        assert_eq!(decode_path("%FF.css"), None);
+        assert_eq!(decode_path("a/%2E%2E/secret"), None);
+        assert_eq!(decode_path("a/../secret"), None);
+        assert_eq!(decode_path("a..b/c"), Some(Cow::Borrowed("a..b/c")));
+    }
+
+    #[test]
+    fn paths_outside_prefix_are_rejected() {
+        let path = EmbedPath::new().with_prefix("/assets");
+        assert_eq!(path.strip_prefix("/style.css"), None);
+        assert_eq!(path.strip_prefix("/assetsstyle.css"), None);
+    }
+}
@@ -0,0 +1,284 @@
+use std::{borrow::Cow, collections::HashMap, sync::RwLock};
+
+use actix_web::web::Bytes;
+use base64::{engine::general_purpose::STANDARD_NO_PAD as Base64Encoder, Engine};
+use lazy_static::lazy_static;
+use sha2::{Digest, Sha256};
+
+use crate::{embed::EmbedRespondable, etag::is_empty_etag};
+
+/// The byte order mark, which some tools put at the start of UTF-8 files.
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// The changes to make to a file before serving it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rewrite<'a> {
+    /// Set the `<base href>` of an HTML file to this path.
+    pub(crate) base_path: Option<&'a str>,
+    /// Remove the UTF-8 byte order mark from the start of a text file.
+    pub(crate) strip_bom: bool,
+}
+
+/// A file with some changes made to it, like an HTML file with its base path
+/// rewritten.
+///
+/// Everything other than the data and the ETag comes from the original file.
+/// There is no precompressed data, since the precompressed versions are of
+/// the original file. It still reports whether the original was precompressed,
+/// so that it's compressed on the fly when the original would be sent
+/// compressed.
+pub(crate) struct RewrittenFile<'a, F: EmbedRespondable> {
+    file: &'a F,
+    data: Bytes,
+    etag: String,
+}
+
+impl<'a, F: EmbedRespondable> EmbedRespondable for RewrittenFile<'a, F> {
+    type Data = Bytes;
+    type DataGzip = Vec<u8>;
+    type DataBr = Vec<u8>;
+    type MimeType = F::MimeType;
+    type ETag = String;
+    type LastModified = F::LastModified;
+
+    fn data(&self) -> Self::Data {
+        self.data.clone()
+    }
+
+    fn data_gzip(&self) -> Option<Self::DataGzip> {
+        None
+    }
+
+    fn data_br(&self) -> Option<Self::DataBr> {
+        None
+    }
+
+    fn is_precompressed_gzip(&self) -> bool {
+        self.file.is_precompressed_gzip()
+    }
+
+    fn is_precompressed_br(&self) -> bool {
+        self.file.is_precompressed_br()
+    }
+
+    fn last_modified_timestamp(&self) -> Option<i64> {
+        self.file.last_modified_timestamp()
+    }
+
+    fn last_modified(&self) -> Option<Self::LastModified> {
+        self.file.last_modified()
+    }
+
+    fn etag(&self) -> Self::ETag {
+        self.etag.clone()
+    }
+
+    fn mime_type(&self) -> Option<Self::MimeType> {
+        self.file.mime_type()
+    }
+
+    fn file_name(&self) -> Option<String> {
+        self.file.file_name()
+    }
+
+    fn cacheable(&self) -> bool {
+        self.file.cacheable()
+    }
+}
+
+/// The rewritten data and its ETag, or `None` if there was nothing to change.
+type Rewritten = Option<(Bytes, String)>;
+
+/// The upstream ETag of the file, the base path and whether the BOM is stripped.
+type RewriteKey = (String, Option<String>, bool);
+
+// Putting the rewritten file into cache could potentially fail. That's okay if
+// it does happen, we'll just rewrite it again next time.
+#[allow(unused_must_use)]
+/// Make the changes to the file, or `None` if there was nothing to change.
+///
+/// The results are cached using the upstream ETag and the changes as the key,
+/// so each file is only rewritten once for each set of changes.
+pub(crate) fn rewrite_file<'a, F: EmbedRespondable>(
+    file: &'a F,
+    changes: Rewrite,
+) -> Option<RewrittenFile<'a, F>> {
+    lazy_static! {
+        static ref CACHED_REWRITES: RwLock<HashMap<RewriteKey, Rewritten>> =
+            RwLock::new(HashMap::new());
+    }
+
+    let upstream = file.etag();
+    // Without an upstream ETag there is nothing to use as the cache key.
+    if !file.cacheable() || is_empty_etag(upstream.as_ref()) {
+        let (data, etag) = rewrite(file.data().as_ref(), changes)?;
+        return Some(RewrittenFile { file, data, etag });
+    }
+
+    let key = (
+        upstream.as_ref().to_string(),
+        changes.base_path.map(ToOwned::to_owned),
+        changes.strip_bom,
+    );
+    let cached = CACHED_REWRITES
+        .read()
+        .ok()
+        .and_then(|cached| cached.get(&key).cloned());
+    let rewritten = match cached {
+        Some(rewritten) => rewritten,
+        None => {
+            let rewritten = rewrite(file.data().as_ref(), changes);
+            CACHED_REWRITES
+                .write()
+                .map(|mut cached| cached.insert(key, rewritten.clone()));
+            rewritten
+        }
+    };
+    rewritten.map(|(data, etag)| RewrittenFile { file, data, etag })
+}
+
+/// Make the changes to the data, and compute the ETag of the result.
+fn rewrite(data: &[u8], changes: Rewrite) -> Rewritten {
+    let mut data = Cow::Borrowed(data);
+    if changes.strip_bom {
+        if let Some(stripped) = data.strip_prefix(UTF8_BOM) {
+            data = Cow::Owned(stripped.to_vec());
+        }
+    }
+    if let Some(base_path) = changes.base_path {
+        if let Some(html) = std::str::from_utf8(&data)
+            .ok()
+            .and_then(|html| rewrite_base_tag(html, base_path))
+        {
+            data = Cow::Owned(html.into_bytes());
+        }
+    }
+
+    match data {
+        Cow::Borrowed(_) => None,
+        Cow::Owned(data) => {
+            let etag = format!("\"{}\"", Base64Encoder.encode(Sha256::digest(&data)));
+            Some((Bytes::from(data), etag))
+        }
+    }
+}
+
+/// Replace the `<base>` tag in the head of the document, or insert one right
+/// after the `<head>` tag if there isn't one.
+fn rewrite_base_tag(html: &str, base_path: &str) -> Option<String> {
+    let tag = format!("<base href=\"{}\">", normalize_base_path(base_path));
+    // ASCII lowercasing keeps the byte offsets the same as in the original.
+    let lowercase = html.to_ascii_lowercase();
+    let head = find_tag(&lowercase, "<head")?;
+    let head_end = lowercase[head..]
+        .find("</head>")
+        .map_or(lowercase.len(), |end| head + end);
+
+    if let Some(start) = find_tag(&lowercase[head..head_end], "<base") {
+        let start = head + start;
+        let end = start + lowercase[start..].find('>')? + 1;
+        return Some(format!("{}{}{}", &html[..start], tag, &html[end..]));
+    }
+
+    let insert_at = head + lowercase[head..].find('>')? + 1;
+    Some(format!(
+        "{}{}{}",
+        &html[..insert_at],
+        tag,
+        &html[insert_at..]
+    ))
+}
+
+/// Find the start of a tag, making sure that it's not just a prefix of a longer
+/// tag name like `<header>` for `<head`.
+fn find_tag(html: &str, tag: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(found) = html[offset..].find(tag) {
+        let start = offset + found;
+        let next = html[start + tag.len()..].chars().next();
+        if matches!(next, Some(c) if c == '>' || c == '/' || c.is_ascii_whitespace()) {
+            return Some(start);
+        }
+        offset = start + tag.len();
+    }
+    None
+}
+
+/// Make sure the base path starts and ends with a `/`, and is safe to put in
+/// the attribute.
+fn normalize_base_path(base_path: &str) -> String {
+    let trimmed = base_path.trim_matches('/');
+    let escaped = trimmed
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;");
+    if escaped.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{escaped}/")
+    }
+}
+
+#[allow(unused_imports)]
+mod test {
+    use super::{rewrite, rewrite_base_tag, Rewrite};
+
+    #[test]
+    fn inserts_base_after_head() {
+        assert_eq!(
+            rewrite_base_tag(
+                "<html><head lang=\"en\"><title>a</title></head></html>",
+                "app"
+            )
+            .as_deref(),
+            Some("<html><head lang=\"en\"><base href=\"/app/\"><title>a</title></head></html>")
+        );
+    }
+
+    #[test]
+    fn replaces_existing_base() {
+        assert_eq!(
+            rewrite_base_tag("<HEAD><BASE href=\"/\"/></HEAD>", "/app/").as_deref(),
+            Some("<HEAD><base href=\"/app/\"></HEAD>")
+        );
+    }
+
+    #[test]
+    fn ignores_header_and_base_outside_head() {
+        assert_eq!(
+            rewrite_base_tag("<header></header><base href=\"/\">", "/app"),
+            None
+        );
+        assert_eq!(
+            rewrite_base_tag("<head></head><base href=\"/\">", "/app").as_deref(),
+            Some("<head><base href=\"/app/\"></head><base href=\"/\">")
+        );
+    }
+
+    #[test]
+    fn escapes_base_path() {
+        assert_eq!(
+            rewrite_base_tag("<head></head>", "a\"b").as_deref(),
+            Some("<head><base href=\"/a&quot;b/\"></head>")
+        );
+    }
+
+    #[test]
+    fn strips_bom_before_rewriting_base() {
+        let changes = Rewrite {
+            base_path: Some("/app/"),
+            strip_bom: true,
+        };
+        let (data, _) = rewrite(b"\xef\xbb\xbf<head></head>", changes).unwrap();
+        assert_eq!(&data[..], b"<head><base href=\"/app/\"></head>");
+    }
+
+    #[test]
+    fn unchanged_data_is_not_rewritten() {
+        let changes = Rewrite {
+            base_path: None,
+            strip_bom: true,
+        };
+        assert_eq!(rewrite(b"{}", changes), None);
+    }
+}
@@ -1,25 +1,21 @@
 use base64::{engine::general_purpose::STANDARD_NO_PAD as Base64Encoder, Engine};
-use chrono::TimeZone;
 use rust_embed::EmbeddedFile;
 use std::{borrow::Cow, ops::Deref};
 
-use crate::embed::{EmbedRespondable, EmbedResponse, IntoResponse};
+use crate::{
+    embed::{EmbedRespondable, EmbedResponse, IntoResponse},
+    helper::format_http_date,
+};
 
 impl From<EmbeddedFile> for EmbedResponse<EmbeddedFile> {
     fn from(file: EmbeddedFile) -> Self {
-        EmbedResponse {
-            file: Some(file),
-            compress: Default::default(),
-        }
+        EmbedResponse::new(Some(file))
     }
 }
 
 impl From<Option<EmbeddedFile>> for EmbedResponse<EmbeddedFile> {
     fn from(file: Option<EmbeddedFile>) -> Self {
-        EmbedResponse {
-            file,
-            compress: Default::default(),
-        }
+        EmbedResponse::new(file)
     }
 }
 
@@ -56,12 +52,7 @@ impl EmbedRespondable for EmbeddedFile {
     }
 
     fn last_modified(&self) -> Option<Self::LastModified> {
-        self.last_modified_timestamp().map(|timestamp| {
-            chrono::Utc
-                .timestamp_opt(timestamp, 0)
-                .unwrap()
-                .to_rfc2822()
-        })
+        self.last_modified_timestamp().and_then(format_http_date)
     }
 
     fn last_modified_timestamp(&self) -> Option<i64> {
@@ -76,6 +67,10 @@ impl EmbedRespondable for EmbeddedFile {
         format!("\"{}\"", Base64Encoder.encode(self.metadata.sha256_hash()))
     }
 
+    fn sha256_hash(&self) -> Option<[u8; 32]> {
+        Some(self.metadata.sha256_hash())
+    }
+
     fn mime_type(&self) -> Option<Self::MimeType> {
         // rust-embed doesn't include the filename for the embedded file, so we
         // can't guess the mime type. We could add `xdg-mime` to guess based on
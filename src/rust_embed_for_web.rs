@@ -24,19 +24,13 @@ pub type EmbedableFileResponse = WebEmbedableFile<EmbeddedFile>;
 
 impl From<EmbeddedFile> for EmbedResponse<WebEmbedableFile<EmbeddedFile>> {
     fn from(file: EmbeddedFile) -> Self {
-        EmbedResponse {
-            file: Some(WebEmbedableFile(file)),
-            compress: Default::default(),
-        }
+        EmbedResponse::new(Some(WebEmbedableFile(file)))
     }
 }
 
 impl From<Option<EmbeddedFile>> for EmbedResponse<WebEmbedableFile<EmbeddedFile>> {
     fn from(file: Option<EmbeddedFile>) -> Self {
-        EmbedResponse {
-            file: file.map(WebEmbedableFile),
-            compress: Default::default(),
-        }
+        EmbedResponse::new(file.map(WebEmbedableFile))
     }
 }
 
@@ -54,19 +48,13 @@ impl IntoResponse<WebEmbedableFile<EmbeddedFile>> for Option<EmbeddedFile> {
 
 impl From<DynamicFile> for EmbedResponse<WebEmbedableFile<DynamicFile>> {
     fn from(file: DynamicFile) -> Self {
-        EmbedResponse {
-            file: Some(WebEmbedableFile(file)),
-            compress: Default::default(),
-        }
+        EmbedResponse::new(Some(WebEmbedableFile(file)))
     }
 }
 
 impl From<Option<DynamicFile>> for EmbedResponse<WebEmbedableFile<DynamicFile>> {
     fn from(file: Option<DynamicFile>) -> Self {
-        EmbedResponse {
-            file: file.map(WebEmbedableFile),
-            compress: Default::default(),
-        }
+        EmbedResponse::new(file.map(WebEmbedableFile))
     }
 }
 
@@ -127,4 +115,13 @@ where
     fn mime_type(&self) -> Option<Self::MimeType> {
         self.0.mime_type()
     }
+
+    fn file_name(&self) -> Option<String> {
+        Some(self.0.name().as_ref().to_string())
+    }
+
+    fn sha256_hash(&self) -> Option<[u8; 32]> {
+        // The hash is stored base85 encoded.
+        base85rs::decode(self.0.hash().as_ref()).and_then(|hash| hash.try_into().ok())
+    }
 }
@@ -0,0 +1,65 @@
+use actix_web::{
+    body::{BodySize, MessageBody},
+    http::StatusCode,
+    HttpRequest, HttpResponse,
+};
+
+/// Information about a response that was served, passed to the callback set
+/// with `EmbedResponse::with_on_response`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ServeInfo {
+    /// The path of the request.
+    pub path: String,
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The `Content-Encoding` of the response, or `None` if the body is not
+    /// compressed.
+    pub encoding: Option<String>,
+    /// The length of the body in bytes, as it would be sent in the
+    /// `Content-Length` header. For `HEAD` requests this is the length the
+    /// body would have had.
+    pub body_length: Option<u64>,
+}
+
+impl ServeInfo {
+    pub(crate) fn new<B: MessageBody>(req: &HttpRequest, resp: &HttpResponse<B>) -> Self {
+        ServeInfo {
+            path: req.path().to_string(),
+            status: resp.status(),
+            encoding: resp
+                .headers()
+                .get("Content-Encoding")
+                .and_then(|v| v.to_str().ok())
+                .map(ToOwned::to_owned),
+            body_length: match resp.body().size() {
+                BodySize::None => Some(0),
+                BodySize::Sized(len) => Some(len),
+                BodySize::Stream => None,
+            },
+        }
+    }
+}
+
+/// Open the span for a response, recording the fields once the response has
+/// been created.
+#[cfg(feature = "tracing")]
+pub(crate) fn response_span(req: &HttpRequest) -> tracing::Span {
+    tracing::info_span!(
+        "embed_response",
+        path = %req.path(),
+        status = tracing::field::Empty,
+        encoding = tracing::field::Empty,
+        content_length = tracing::field::Empty,
+    )
+}
+
+/// Record how the request was answered in the span from `response_span`.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_response(span: &tracing::Span, info: &ServeInfo) {
+    span.record("status", info.status.as_u16());
+    span.record("encoding", info.encoding.as_deref().unwrap_or("identity"));
+    if let Some(len) = info.body_length {
+        span.record("content_length", len);
+    }
+}
@@ -0,0 +1,108 @@
+/// Guess the mime type of the data from its first bytes, for a small set of
+/// formats that are easy to recognize.
+fn sniff(data: &[u8]) -> Option<&'static str> {
+    const MAGIC: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"\0asm", "application/wasm"),
+    ];
+    if let Some((_, mime_type)) = MAGIC.iter().find(|(magic, _)| data.starts_with(magic)) {
+        return Some(mime_type);
+    }
+    if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let text = &data[start..];
+    ["<!doctype html", "<html", "<head", "<script"]
+        .iter()
+        .any(|tag| {
+            text.get(..tag.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(tag.as_bytes()))
+        })
+        .then_some("text/html")
+}
+
+/// Whether the data clearly isn't of the declared mime type.
+///
+/// Only the formats that `sniff` can recognize are checked, so this never
+/// refuses a type it knows nothing about. `text/plain` is never refused, since
+/// browsers won't run or render anything served as plain text.
+pub(crate) fn conflicts_with_content_type(content_type: &str, data: &[u8]) -> bool {
+    const CHECKED: &[&str] = &[
+        "text/html",
+        "text/css",
+        "text/javascript",
+        "application/javascript",
+        "application/json",
+        "image/svg+xml",
+        "image/png",
+        "image/jpeg",
+        "image/gif",
+        "image/webp",
+        "application/pdf",
+        "application/zip",
+        "application/gzip",
+        "application/wasm",
+    ];
+    let declared = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if !CHECKED.contains(&declared.as_str()) {
+        return false;
+    }
+    match sniff(data) {
+        Some(sniffed) => sniffed != declared,
+        None => false,
+    }
+}
+
+#[allow(unused_imports)]
+mod test {
+    use super::conflicts_with_content_type;
+
+    #[test]
+    fn html_declared_for_png_conflicts() {
+        assert!(conflicts_with_content_type(
+            "text/html; charset=utf-8",
+            b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"
+        ));
+    }
+
+    #[test]
+    fn png_declared_for_html_conflicts() {
+        assert!(conflicts_with_content_type(
+            "image/png",
+            b"\n  <!DOCTYPE html><html></html>"
+        ));
+    }
+
+    #[test]
+    fn matching_and_unknown_types_pass() {
+        assert!(!conflicts_with_content_type(
+            "text/html",
+            b"<!doctype html>"
+        ));
+        assert!(!conflicts_with_content_type(
+            "text/css",
+            b"body { color: red }"
+        ));
+        assert!(!conflicts_with_content_type("text/plain", b"<html></html>"));
+        assert!(!conflicts_with_content_type(
+            "application/octet-stream",
+            b"\x89PNG\r\n\x1a\n"
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::{
+    embed::{EmbedRespondable, EmbedResponse},
+    etag::is_empty_etag,
+    is_well_known_compressible_mime_type,
+};
+
+/// A problem found with an embedded file by `EmbedValidator`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbedValidationError {
+    /// The file is listed, but the embed doesn't return it.
+    Missing { path: String },
+    /// The ETag of the file is empty.
+    EmptyEtag { path: String },
+    /// Neither the embed nor the path of the file reveal its mime type, so it
+    /// would be served without a `Content-Type`.
+    UnknownMimeType { path: String },
+    /// The file is compressible and larger than the precompression threshold,
+    /// but the embed has no precompressed version of it.
+    MissingPrecompression { path: String },
+}
+
+impl fmt::Display for EmbedValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbedValidationError::Missing { path } => write!(f, "{path}: file is missing"),
+            EmbedValidationError::EmptyEtag { path } => write!(f, "{path}: ETag is empty"),
+            EmbedValidationError::UnknownMimeType { path } => {
+                write!(f, "{path}: mime type is unknown")
+            }
+            EmbedValidationError::MissingPrecompression { path } => {
+                write!(f, "{path}: file has not been precompressed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EmbedValidationError {}
+
+/// Checks that the files in an embed can be served properly, so that a broken
+/// embed can be caught at startup instead of when the files are requested.
+///
+/// Call this in `main` before starting the server.
+///
+/// ```ignore
+/// EmbedValidator::new()
+///     .with_precompression_threshold(1024)
+///     .validate::<Assets>()
+///     .expect("the embedded assets are broken");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmbedValidator {
+    precompression_threshold: Option<usize>,
+}
+
+impl EmbedValidator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Require a precompressed version of compressible files that are larger
+    /// than `threshold` bytes.
+    ///
+    /// Only `rust-embed-for-web` precompresses files, and only in release
+    /// builds or with the `always-embed` feature, so this check is off by
+    /// default.
+    pub fn with_precompression_threshold(mut self, threshold: usize) -> Self {
+        self.precompression_threshold = Some(threshold);
+        self
+    }
+
+    /// Validate every file in a `rust-embed` embed.
+    #[cfg(feature = "support-rust-embed")]
+    pub fn validate<A: rust_embed::RustEmbed>(&self) -> Result<(), Vec<EmbedValidationError>> {
+        self.validate_files(A::iter(), A::get)
+    }
+
+    /// Validate the files at `paths`, looking them up with `get`.
+    ///
+    /// `get` is the getter of your embed, like `Embed::get`. This is useful
+    /// with `rust-embed-for-web`, which can't list the files in an embed.
+    pub fn validate_files<T, F, G, I, S>(
+        &self,
+        paths: I,
+        get: G,
+    ) -> Result<(), Vec<EmbedValidationError>>
+    where
+        T: EmbedRespondable,
+        G: Fn(&str) -> Option<F>,
+        Option<F>: Into<EmbedResponse<T>>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let errors: Vec<EmbedValidationError> = paths
+            .into_iter()
+            .flat_map(|path| self.validate_file(path.as_ref(), get(path.as_ref()).into()))
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_file<T: EmbedRespondable>(
+        &self,
+        path: &str,
+        response: EmbedResponse<T>,
+    ) -> Vec<EmbedValidationError> {
+        let path = path.to_string();
+        let Some(file) = response.file() else {
+            return vec![EmbedValidationError::Missing { path }];
+        };
+
+        let mut errors = Vec::new();
+        let etag = file.etag();
+        if is_empty_etag(etag.as_ref()) {
+            errors.push(EmbedValidationError::EmptyEtag { path: path.clone() });
+        }
+
+        // rust-embed doesn't guess mime types, but it will be served with one
+        // if the path of the request reveals it.
+        let mime_type = file
+            .mime_type()
+            .map(|v| v.as_ref().to_string())
+            .or_else(|| mime_guess::from_path(&path).first_raw().map(String::from));
+        match mime_type.as_deref() {
+            None | Some("application/octet-stream") => {
+                errors.push(EmbedValidationError::UnknownMimeType { path: path.clone() })
+            }
+            Some(mime_type) => {
+                if let Some(threshold) = self.precompression_threshold {
+                    if file.data().as_ref().len() > threshold
+                        && is_well_known_compressible_mime_type(mime_type)
+                        && file.data_gzip().is_none()
+                    {
+                        errors.push(EmbedValidationError::MissingPrecompression { path });
+                    }
+                }
+            }
+        }
+        errors
+    }
+}
+
+/// Validate every file in a `rust-embed` embed with the default settings.
+///
+/// This is a shortcut for `EmbedValidator::new().validate::<A>()`.
+#[cfg(feature = "support-rust-embed")]
+pub fn validate_embed<A: rust_embed::RustEmbed>() -> Result<(), Vec<EmbedValidationError>> {
+    EmbedValidator::new().validate::<A>()
+}
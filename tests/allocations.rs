@@ -0,0 +1,121 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use actix_web::{body::MessageBody, test::TestRequest, Responder};
+use actix_web_rust_embed_responder::{
+    Compress, EmbedResponse, EtagAlgorithm, IntoResponse, MemoryFile,
+};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+/// Counts the allocations made by the current thread and their total size, so
+/// that tests running in parallel don't get in each other's way.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        ALLOCATED_BYTES.with(|bytes| bytes.set(bytes.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (usize, R) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (ALLOCATIONS.with(Cell::get) - before, result)
+}
+
+fn allocated_bytes<R>(f: impl FnOnce() -> R) -> (usize, R) {
+    let before = ALLOCATED_BYTES.with(Cell::get);
+    let result = f();
+    (ALLOCATED_BYTES.with(Cell::get) - before, result)
+}
+
+const NOT_FOUND: EmbedResponse<MemoryFile> = EmbedResponse::new(None);
+
+#[test]
+fn creating_a_response_does_not_allocate() {
+    let file = EmbedREFW::get("style.css");
+    let (count, resp) = allocations(|| {
+        file.into_response()
+            .use_compression(Compress::Always)
+            .with_etag_algorithm(EtagAlgorithm::Sha256Hex)
+            .with_strict_conditionals(true)
+    });
+    assert_eq!(count, 0);
+    assert!(resp.file().is_some());
+
+    let file = MemoryFile::new(&b"hello world"[..], "hello");
+    let (count, _) = allocations(|| file.into_response());
+    assert_eq!(count, 0);
+}
+
+#[actix_web::test]
+async fn const_response_is_a_404() {
+    let req = TestRequest::get().to_http_request();
+    let resp = NOT_FOUND.respond_to(&req);
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn memory_file_data_is_not_copied() {
+    const SIZE: usize = 1 << 20;
+    let file = MemoryFile::new(vec![b'a'; SIZE], "large");
+    let req = TestRequest::get().uri("/large.txt").to_http_request();
+
+    // Check the ETag with a hash too, which reads the data separately from
+    // sending it.
+    let (bytes, resp) = allocated_bytes(|| {
+        file.into_response()
+            .use_compression(Compress::Never)
+            .with_etag_algorithm(EtagAlgorithm::Sha256Hex)
+            .respond_to(&req)
+    });
+    assert_eq!(resp.status(), 200);
+    assert!(bytes < SIZE, "{bytes} bytes were allocated");
+}
+
+#[actix_web::test]
+async fn streamed_chunks_are_not_copied() {
+    const SIZE: usize = 1 << 20;
+    let file = MemoryFile::new(vec![b'a'; SIZE], "large");
+    let req = TestRequest::get().uri("/large.txt").to_http_request();
+    let mut body = file
+        .into_response()
+        .use_compression(Compress::Never)
+        .with_auto_streaming(16 * 1024)
+        .respond_to(&req)
+        .into_body();
+
+    let (bytes, chunks) = allocated_bytes(|| {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut chunks = 0;
+        while let Poll::Ready(Some(chunk)) = Pin::new(&mut body).poll_next(&mut cx) {
+            assert!(!chunk.unwrap().is_empty());
+            chunks += 1;
+        }
+        chunks
+    });
+    assert!(chunks > 1);
+    assert!(bytes < SIZE, "{bytes} bytes were allocated");
+}
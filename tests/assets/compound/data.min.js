@@ -0,0 +1,4 @@
+const data={items:[1,2,3,4,5,6,7,8,9,10]};export default data;
+const data={items:[1,2,3,4,5,6,7,8,9,10]};export default data;
+const data={items:[1,2,3,4,5,6,7,8,9,10]};export default data;
+const data={items:[1,2,3,4,5,6,7,8,9,10]};export default data;
@@ -0,0 +1,91 @@
+use std::{future::poll_fn, pin::Pin};
+
+use actix_web::body::{BodySize, BoxBody, MessageBody};
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+const LARGE: &str = "pexels-david-yu-10075042.jpg";
+
+/// Read the body, returning the chunks it was sent in.
+async fn read_chunks(mut body: BoxBody) -> Vec<Vec<u8>> {
+    let mut chunks = Vec::new();
+    while let Some(chunk) = poll_fn(|cx| Pin::new(&mut body).poll_next(cx)).await {
+        chunks.push(chunk.unwrap().to_vec());
+    }
+    chunks
+}
+
+#[actix_web::test]
+async fn large_files_are_streamed() {
+    let data = EmbedREFW::get(LARGE).unwrap().data();
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get(LARGE)
+        .into_response()
+        .with_auto_streaming(16 * 1024)
+        .respond_to(&req);
+    assert_eq!(resp.body().size(), BodySize::Sized(data.len() as u64));
+    let chunks = read_chunks(resp.into_body()).await;
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.concat(), data);
+}
+
+#[actix_web::test]
+async fn small_files_are_sent_at_once() {
+    let data = EmbedREFW::get("style.css").unwrap().data();
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_auto_streaming(16 * 1024)
+        .respond_to(&req);
+    let chunks = read_chunks(resp.into_body()).await;
+    assert_eq!(chunks, vec![data]);
+}
+
+#[actix_web::test]
+async fn ranges_are_streamed_by_their_own_size() {
+    let data = EmbedREFW::get(LARGE).unwrap().data();
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-99"))
+        .to_http_request();
+    let resp = EmbedREFW::get(LARGE)
+        .into_response()
+        .with_auto_streaming(16 * 1024)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    let chunks = read_chunks(resp.into_body()).await;
+    assert_eq!(chunks, vec![data[..100].to_vec()]);
+
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-"))
+        .to_http_request();
+    let resp = EmbedREFW::get(LARGE)
+        .into_response()
+        .with_auto_streaming(16 * 1024)
+        .respond_to(&req);
+    let chunks = read_chunks(resp.into_body()).await;
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.concat(), data);
+}
+
+#[actix_web::test]
+async fn compressed_bodies_are_streamed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get(LARGE)
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_gzip_excluded_types(&[])
+        .with_auto_streaming(16 * 1024)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    let size = resp.body().size();
+    let chunks = read_chunks(resp.into_body()).await;
+    assert!(chunks.len() > 1);
+    assert_eq!(size, BodySize::Sized(chunks.concat().len() as u64));
+}
@@ -0,0 +1,166 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{Compress, EmbedRespondable, EmbedResponse, IntoResponse};
+use flate2::read::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+use std::io::Read;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn base_tag_is_added_to_html() {
+    let req = test::TestRequest::get().to_http_request();
+    let original = EmbedREFW::get("index.html")
+        .into_response()
+        .respond_to(&req);
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_base_path("/app")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    assert_ne!(&etag, original.headers().get("ETag").unwrap());
+
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    let body = std::str::from_utf8(&body).unwrap();
+    assert!(body.contains("<head><base href=\"/app/\">"));
+
+    // The ETag stays the same as long as the base path does.
+    // rust-embed doesn't know the type of the file, so it has to be set.
+    let resp = EmbedRE::get("index.html")
+        .into_response()
+        .with_content_type("text/html")
+        .with_base_path("/app/")
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("ETag").unwrap(), &etag);
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_base_path("/other/")
+        .respond_to(&req);
+    assert_ne!(resp.headers().get("ETag").unwrap(), &etag);
+}
+
+#[actix_web::test]
+async fn rewritten_etag_is_used_for_conditional_requests() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_base_path("/app/")
+        .respond_to(&req);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_base_path("/app/")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+/// An HTML file with precompressed versions, like `rust-embed-for-web` files
+/// in release builds.
+struct PrecompressedHtml;
+
+impl EmbedRespondable for PrecompressedHtml {
+    type Data = &'static [u8];
+    type DataGzip = &'static [u8];
+    type DataBr = &'static [u8];
+    type MimeType = &'static str;
+    type ETag = &'static str;
+    type LastModified = &'static str;
+
+    fn data(&self) -> Self::Data {
+        b"<html><head><title>a</title></head></html>"
+    }
+
+    // The rewritten file can't use these, so their contents don't matter.
+    fn data_gzip(&self) -> Option<Self::DataGzip> {
+        Some(b"precompressed")
+    }
+
+    fn data_br(&self) -> Option<Self::DataBr> {
+        None
+    }
+
+    fn last_modified_timestamp(&self) -> Option<i64> {
+        None
+    }
+
+    fn last_modified(&self) -> Option<Self::LastModified> {
+        None
+    }
+
+    fn etag(&self) -> Self::ETag {
+        "\"precompressed-html\""
+    }
+
+    fn mime_type(&self) -> Option<Self::MimeType> {
+        Some("text/html")
+    }
+}
+
+#[actix_web::test]
+async fn rewritten_html_is_compressed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    // The precompressed versions can't be used, so the rewritten file is
+    // compressed on the fly with the default settings instead.
+    let resp = EmbedResponse::new(Some(PrecompressedHtml))
+        .with_base_path("/app/")
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Encoding");
+
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    let mut decompressed = String::new();
+    GzDecoder::new(&body[..])
+        .read_to_string(&mut decompressed)
+        .unwrap();
+    assert!(decompressed.contains("<head><base href=\"/app/\">"));
+}
+
+#[actix_web::test]
+async fn rewritten_html_follows_the_original_compression() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    // rust-embed files aren't precompressed, so they aren't compressed unless
+    // compression is turned on for them.
+    let resp = EmbedRE::get("index.html")
+        .into_response()
+        .with_content_type("text/html")
+        .with_base_path("/app/")
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Never)
+        .with_base_path("/app/")
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn other_files_are_unchanged() {
+    let req = test::TestRequest::get().to_http_request();
+    let file = EmbedREFW::get("style.css").unwrap();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_base_path("/app/")
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("ETag").unwrap(),
+        &file.etag().to_string()
+    );
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, file.data());
+}
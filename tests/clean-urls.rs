@@ -0,0 +1,77 @@
+use actix_web::{body, test, web, App, HttpRequest};
+use actix_web_rust_embed_responder::{EmbedPath, EmbedResponse, EmbedableFileResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/site/"]
+struct Site;
+
+async fn handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new()
+        .with_clean_urls(true)
+        .resolve(&req, Site::get)
+}
+
+async fn get(path: &str) -> (u16, String) {
+    let app = test::init_service(App::new().default_service(web::to(handler))).await;
+    let req = test::TestRequest::get().uri(path).to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status().as_u16();
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[actix_web::test]
+async fn exact_path_is_served() {
+    let (status, body) = get("/about.html").await;
+    assert_eq!(status, 200);
+    assert!(body.contains("<title>About</title>"));
+}
+
+#[actix_web::test]
+async fn html_sibling_is_served() {
+    let app = test::init_service(App::new().default_service(web::to(handler))).await;
+    let req = test::TestRequest::get().uri("/about").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/html");
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert!(std::str::from_utf8(&body)
+        .unwrap()
+        .contains("<title>About</title>"));
+
+    // The resolved file still answers conditional requests.
+    let req = test::TestRequest::get()
+        .uri("/about")
+        .append_header(("If-None-Match", etag))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn directory_index_is_served() {
+    for path in ["/docs", "/docs/"] {
+        let (status, body) = get(path).await;
+        assert_eq!(status, 200, "{path}");
+        assert!(body.contains("<title>Docs</title>"), "{path}");
+    }
+}
+
+#[actix_web::test]
+async fn missing_paths_are_not_found() {
+    assert_eq!(get("/missing").await.0, 404);
+    assert_eq!(get("/docs/missing").await.0, 404);
+}
+
+#[actix_web::test]
+async fn clean_urls_are_off_by_default() {
+    let app = test::init_service(App::new().default_service(web::to(
+        |req: HttpRequest| async move { EmbedPath::new().resolve(&req, Site::get) },
+    )))
+    .await;
+    let req = test::TestRequest::get().uri("/docs").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
@@ -0,0 +1,98 @@
+use actix_web::{test, App, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse, MemoryFile, MimeRegistry};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/compound/"]
+struct EmbedREFW;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "tests/assets/compound/"]
+struct EmbedRE;
+
+fn gzip_request(path: &str) -> actix_web::HttpRequest {
+    test::TestRequest::get()
+        .uri(path)
+        .append_header(("Accept-Encoding", "gzip, br"))
+        .to_http_request()
+}
+
+#[actix_web::test]
+async fn content_type_uses_the_last_extension() {
+    let cases = [
+        ("archive.tar.gz", "application/gzip"),
+        // The same as for any other `.js` file.
+        ("data.min.js", "text/javascript"),
+        ("theme.user.css", "text/css"),
+    ];
+    for (path, content_type) in cases {
+        let resp = EmbedREFW::get(path)
+            .into_response()
+            .respond_to(&gzip_request(path));
+        assert_eq!(
+            resp.headers().get("Content-Type").unwrap(),
+            content_type,
+            "{path}"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn gzip_files_are_not_compressed_again() {
+    let resp = EmbedREFW::get("archive.tar.gz")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_gzip_excluded_types(&[])
+        .respond_to(&gzip_request("/archive.tar.gz"));
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+
+    // rust-embed doesn't know the name or type of the file, so the request
+    // path is used.
+    let resp = EmbedRE::get("archive.tar.gz")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&gzip_request("/archive.tar.gz"));
+    assert!(resp.headers().get("Content-Encoding").is_none());
+
+    let resp = MemoryFile::new(&b"not really brotli"[..], "sidecar")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&gzip_request("/app.js.br"));
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn minified_and_user_files_are_compressed() {
+    for path in ["data.min.js", "theme.user.css"] {
+        let resp = EmbedREFW::get(path)
+            .into_response()
+            .use_compression(Compress::Always)
+            .respond_to(&gzip_request(path));
+        assert!(resp.headers().get("Content-Encoding").is_some(), "{path}");
+    }
+}
+
+#[actix_web::test]
+async fn registry_matches_compound_extensions() {
+    let app = test::init_service(
+        App::new()
+            .app_data(
+                MimeRegistry::new()
+                    .with_mapping("gz", "application/gzip")
+                    .with_mapping("tar.gz", "application/x-gtar"),
+            )
+            .default_service(actix_web::web::to(
+                |req: actix_web::HttpRequest| async move {
+                    EmbedREFW::get(req.path().trim_start_matches('/')).into_response()
+                },
+            )),
+    )
+    .await;
+    let req = test::TestRequest::get().uri("/archive.tar.gz").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/x-gtar"
+    );
+}
@@ -0,0 +1,68 @@
+use std::io::Write;
+
+use actix_web::test;
+use actix_web::{middleware, route, web, App};
+use actix_web_rust_embed_responder::{
+    Compress, EmbedResponse, EmbedableFileResponse, IntoResponse,
+};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[route("/{path:.*}", method = "GET", method = "HEAD")]
+async fn handler(path: web::Path<String>) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedREFW::get(&path)
+        .into_response()
+        .use_compression(Compress::Always)
+}
+
+#[actix_web::test]
+async fn compressed_responses_are_not_compressed_again() {
+    let app = test::init_service(
+        App::new()
+            .wrap(middleware::Compress::default())
+            .service(handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/index.html")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    let body = test::read_body(resp).await;
+
+    // Decompressing once must give back the original file.
+    let mut decompressed = Vec::new();
+    flate2::write::GzDecoder::new(&mut decompressed)
+        .write_all(&body)
+        .unwrap();
+    let original = EmbedREFW::get("index.html").unwrap().data();
+    assert_eq!(&decompressed[..], AsRef::<[u8]>::as_ref(&original));
+}
+
+#[actix_web::test]
+async fn compressed_responses_have_a_content_length() {
+    let app = test::init_service(App::new().service(handler)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/index.html")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert!(resp.headers().get("Transfer-Encoding").is_none());
+    let content_length: usize = resp
+        .headers()
+        .get("Content-Length")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let body = test::read_body(resp).await;
+    assert_eq!(content_length, body.len());
+}
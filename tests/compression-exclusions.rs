@@ -0,0 +1,52 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/images/"]
+struct EmbedREFW;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedAssets;
+
+#[actix_web::test]
+async fn images_are_not_compressed_by_default() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "br, gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("pixel.png")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "image/png");
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn exclusions_can_be_replaced() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("pixel.png")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_gzip_excluded_types(&[])
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+}
+
+#[actix_web::test]
+async fn custom_exclusions_replace_the_defaults() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedAssets::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_gzip_excluded_types(&["text/css"])
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
@@ -0,0 +1,274 @@
+use actix_web::test;
+use actix_web::Responder;
+use actix_web_rust_embed_responder::{
+    Compress, EmbedRespondable, EmbedResponse, IntoResponse, MemoryFile,
+};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn not_modified_has_no_content_encoding() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .append_header(("If-None-Match", etag.clone()))
+        .to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert!(resp.headers().get("Content-Length").is_none());
+    assert!(resp.headers().get("Content-Type").is_none());
+    assert_eq!(resp.headers().get("ETag").unwrap(), etag);
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-cache");
+}
+
+#[actix_web::test]
+async fn not_modified_has_no_content_encoding_with_on_the_fly_compression() {
+    let file = EmbedRE::get("index.html").unwrap();
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = file
+        .clone()
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = file
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert!(resp.headers().get("Content-Length").is_none());
+}
+
+fn dated_file() -> MemoryFile {
+    // Wed, 21 Oct 2015 07:28:00 GMT
+    MemoryFile::new(&b"hello world"[..], "hello").with_last_modified_timestamp(1445412480)
+}
+
+#[actix_web::test]
+async fn gmt_dates_are_compared() {
+    let req = test::TestRequest::get()
+        .append_header(("If-Modified-Since", "Wed, 21 Oct 2015 07:28:00 GMT"))
+        .to_http_request();
+    let resp = dated_file().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn dates_with_other_timezones_are_ignored() {
+    // This is the same moment as the last modification, but HTTP-dates
+    // must be in GMT so the header is ignored.
+    let req = test::TestRequest::get()
+        .append_header(("If-Modified-Since", "Wed, 21 Oct 2015 09:28:00 +0200"))
+        .to_http_request();
+    let resp = dated_file().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn precondition_matrix() {
+    const BEFORE: &str = "Tue, 20 Oct 2015 07:28:00 GMT";
+    const AT: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+    const MATCHING: &str = "\"hello\"";
+    const OTHER: &str = "\"other\"";
+
+    // (If-None-Match, If-Unmodified-Since, If-Modified-Since, status)
+    type Case = (
+        Option<&'static str>,
+        Option<&'static str>,
+        Option<&'static str>,
+        u16,
+    );
+    let cases: &[Case] = &[
+        (None, None, None, 200),
+        (Some(MATCHING), None, None, 304),
+        (Some(OTHER), None, None, 200),
+        (None, Some(AT), None, 200),
+        (None, Some(BEFORE), None, 412),
+        (None, None, Some(AT), 304),
+        (None, None, Some(BEFORE), 200),
+        // If-Unmodified-Since is checked before anything else.
+        (Some(MATCHING), Some(BEFORE), None, 412),
+        (Some(OTHER), Some(BEFORE), None, 412),
+        (Some(MATCHING), Some(AT), None, 304),
+        (Some(OTHER), Some(AT), None, 200),
+        // If-Modified-Since is ignored when there is an If-None-Match.
+        (Some(OTHER), None, Some(AT), 200),
+        (Some(MATCHING), None, Some(BEFORE), 304),
+        (None, Some(AT), Some(AT), 304),
+        (None, Some(BEFORE), Some(AT), 412),
+        // `*` matches any file that exists.
+        (Some("*"), None, None, 304),
+        (Some("*"), None, Some(BEFORE), 304),
+        (Some("*"), Some(BEFORE), None, 412),
+    ];
+
+    for (if_none_match, if_unmodified_since, if_modified_since, status) in cases {
+        let mut req = test::TestRequest::get();
+        if let Some(value) = if_none_match {
+            req = req.append_header(("If-None-Match", *value));
+        }
+        if let Some(value) = if_unmodified_since {
+            req = req.append_header(("If-Unmodified-Since", *value));
+        }
+        if let Some(value) = if_modified_since {
+            req = req.append_header(("If-Modified-Since", *value));
+        }
+        let resp = dated_file()
+            .into_response()
+            .respond_to(&req.to_http_request());
+        assert_eq!(
+            resp.status().as_u16(),
+            *status,
+            "If-None-Match: {if_none_match:?}, If-Unmodified-Since: {if_unmodified_since:?}, If-Modified-Since: {if_modified_since:?}"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn date_conditionals_are_skipped_without_a_timestamp() {
+    let undated = || MemoryFile::new(&b"hello world"[..], "hello");
+    for name in ["If-Modified-Since", "If-Unmodified-Since"] {
+        let req = test::TestRequest::get()
+            .append_header((name, "Wed, 21 Oct 2015 07:28:00 GMT"))
+            .to_http_request();
+        let resp = undated().into_response().respond_to(&req);
+        assert_eq!(resp.status(), 200, "{name}");
+        assert!(resp.headers().get("Last-Modified").is_none());
+    }
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"hello\""))
+        .append_header(("If-Modified-Since", "Wed, 21 Oct 2015 07:28:00 GMT"))
+        .to_http_request();
+    let resp = undated().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn if_none_match_ignores_whitespace_around_etags() {
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"other\" ,\t\"hello\"\t, \"more\""))
+        .to_http_request();
+    let resp = dated_file().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+/// A file with a formatted `Last-Modified` date, but no timestamp.
+struct DateOnly;
+
+impl EmbedRespondable for DateOnly {
+    type Data = &'static [u8];
+    type DataGzip = &'static [u8];
+    type DataBr = &'static [u8];
+    type MimeType = &'static str;
+    type ETag = &'static str;
+    type LastModified = &'static str;
+
+    fn data(&self) -> Self::Data {
+        b"hello world"
+    }
+
+    fn data_gzip(&self) -> Option<Self::DataGzip> {
+        None
+    }
+
+    fn data_br(&self) -> Option<Self::DataBr> {
+        None
+    }
+
+    fn last_modified_timestamp(&self) -> Option<i64> {
+        None
+    }
+
+    fn last_modified(&self) -> Option<Self::LastModified> {
+        Some("Wed, 21 Oct 2015 07:28:00 GMT")
+    }
+
+    fn etag(&self) -> Self::ETag {
+        "\"hello\""
+    }
+
+    fn mime_type(&self) -> Option<Self::MimeType> {
+        Some("text/plain")
+    }
+}
+
+#[actix_web::test]
+async fn date_conditionals_need_a_timestamp_even_with_a_date() {
+    for date in [
+        "Wed, 21 Oct 2015 07:28:00 GMT",
+        "Thu, 01 Jan 2099 00:00:00 GMT",
+    ] {
+        let req = test::TestRequest::get()
+            .append_header(("If-Modified-Since", date))
+            .to_http_request();
+        let resp = EmbedResponse::new(Some(DateOnly)).respond_to(&req);
+        assert_eq!(resp.status(), 200, "{date}");
+        assert_eq!(
+            resp.headers().get("Last-Modified").unwrap(),
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"hello\""))
+        .append_header(("If-Modified-Since", "Tue, 20 Oct 2015 07:28:00 GMT"))
+        .to_http_request();
+    let resp = EmbedResponse::new(Some(DateOnly)).respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn echoed_last_modified_is_not_modified() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    let last_modified = resp.headers().get("Last-Modified").unwrap().clone();
+
+    for strict in [false, true] {
+        let req = test::TestRequest::get()
+            .append_header(("If-Modified-Since", last_modified.clone()))
+            .to_http_request();
+        let resp = EmbedREFW::get("style.css")
+            .into_response()
+            .with_strict_conditionals(strict)
+            .respond_to(&req);
+        assert_eq!(resp.status(), 304, "strict: {strict}");
+
+        let req = test::TestRequest::get()
+            .append_header(("If-Unmodified-Since", last_modified.clone()))
+            .to_http_request();
+        let resp = EmbedREFW::get("style.css")
+            .into_response()
+            .with_strict_conditionals(strict)
+            .respond_to(&req);
+        assert_eq!(resp.status(), 200, "strict: {strict}");
+    }
+}
@@ -0,0 +1,50 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/images/"]
+struct Images;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn mismatched_type_is_refused() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = Images::get("pixel.png")
+        .into_response()
+        .with_content_type("text/html")
+        .with_content_type_guard(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 500);
+    assert!(resp.headers().get("Content-Type").is_none());
+}
+
+#[actix_web::test]
+async fn mismatched_type_is_served_without_the_guard() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = Images::get("pixel.png")
+        .into_response()
+        .with_content_type("text/html")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn matching_types_are_served() {
+    let req = test::TestRequest::get().to_http_request();
+    for path in ["index.html", "style.css", "pexels-david-yu-10075042.jpg"] {
+        let resp = EmbedREFW::get(path)
+            .into_response()
+            .with_content_type_guard(true)
+            .respond_to(&req);
+        assert_eq!(resp.status(), 200, "{path}");
+    }
+    let resp = Images::get("pixel.png")
+        .into_response()
+        .with_content_type_guard(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
@@ -0,0 +1,43 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "tests/assets/hashed/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/hashed/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn content_type_is_guessed_from_the_request_path() {
+    let req = test::TestRequest::get()
+        .uri("/assets/style.abc123.css")
+        .to_http_request();
+    let resp = EmbedREFW::get("abc123")
+        .into_response()
+        .with_request_path_for_content_type()
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/css");
+
+    let resp = EmbedRE::get("abc123")
+        .into_response()
+        .with_request_path_for_content_type()
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/css");
+}
+
+#[actix_web::test]
+async fn request_path_is_not_used_by_default() {
+    let req = test::TestRequest::get()
+        .uri("/assets/style.abc123.css")
+        .to_http_request();
+    let resp = EmbedREFW::get("abc123").into_response().respond_to(&req);
+    assert_ne!(
+        resp.headers()
+            .get("Content-Type")
+            .map(|v| v.to_str().unwrap()),
+        Some("text/css")
+    );
+}
@@ -0,0 +1,91 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse, ResponderDefaults};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn downloads_are_uncompressed_attachments() {
+    let req = test::TestRequest::get()
+        .app_data(ResponderDefaults::new().use_compression(Compress::Always))
+        .append_header(("Accept-Encoding", "br, gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .download()
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert_eq!(
+        resp.headers().get("Content-Disposition").unwrap(),
+        "attachment; filename=\"style.css\""
+    );
+    assert_eq!(resp.headers().get("Accept-Ranges").unwrap(), "bytes");
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, EmbedREFW::get("style.css").unwrap().data());
+}
+
+#[actix_web::test]
+async fn downloads_can_be_resumed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .append_header(("Range", "bytes=10-"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .download()
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    assert!(resp.headers().get("Content-Disposition").is_some());
+}
+
+#[actix_web::test]
+async fn downloads_turn_ranges_back_on() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=10-"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_ranges(false)
+        .download()
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    assert_eq!(resp.headers().get("Accept-Ranges").unwrap(), "bytes");
+}
+
+#[actix_web::test]
+async fn attachment_names() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedRE::get("style.css")
+        .into_response()
+        .with_attachment()
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Content-Disposition").unwrap(),
+        "attachment"
+    );
+
+    let resp = EmbedRE::get("style.css")
+        .into_response()
+        .with_attachment_name("my \"style\".css")
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Content-Disposition").unwrap(),
+        "attachment; filename=\"my \\\"style\\\".css\""
+    );
+
+    let resp = EmbedRE::get("style.css")
+        .into_response()
+        .with_attachment_name("stíl.css")
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Content-Disposition").unwrap(),
+        "attachment; filename=\"st_l.css\"; filename*=UTF-8''st%C3%ADl.css"
+    );
+}
@@ -0,0 +1,41 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{IntoResponse, LinkHeader};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn early_hints_are_sent_as_link_headers() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_early_hints(vec![
+            LinkHeader::preload("/style.css").with_as("style"),
+            LinkHeader::preload("/pexels-yana-moroz-12639653.jpg").with_as("image"),
+        ])
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    let links: Vec<&str> = resp
+        .headers()
+        .get_all("Link")
+        .map(|v| v.to_str().unwrap())
+        .collect();
+    assert_eq!(
+        links,
+        vec![
+            "</style.css>; rel=preload; as=style",
+            "</pexels-yana-moroz-12639653.jpg>; rel=preload; as=image",
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn no_link_headers_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .respond_to(&req);
+    assert!(resp.headers().get("Link").is_none());
+}
@@ -0,0 +1,48 @@
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "tests/assets/empty/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/empty/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn empty_file_is_served_uncompressed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "br, gzip"))
+        .to_http_request();
+    let resp = EmbedRE::get("empty.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert!(resp.headers().get("ETag").is_some());
+    assert_eq!(resp.body().size(), BodySize::Sized(0));
+
+    let resp = EmbedREFW::get("empty.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert_eq!(resp.body().size(), BodySize::Sized(0));
+}
+
+#[actix_web::test]
+async fn empty_file_can_be_revalidated() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("empty.css").into_response().respond_to(&req);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("empty.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
@@ -0,0 +1,260 @@
+//! Requests going through a whole app, to catch the interactions between
+//! headers that the tests for single features don't.
+
+use actix_http::body::MessageBody;
+use actix_web::{
+    dev::{ServiceFactory, ServiceRequest, ServiceResponse},
+    http::Method,
+    test, web, App, HttpRequest,
+};
+use actix_web_rust_embed_responder::{
+    Compress, EmbedPath, EmbedResponse, EmbedableFileResponse, Encoding,
+};
+use flate2::read::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+use std::io::Read;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct Assets;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/site/"]
+struct Site;
+
+async fn assets(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new()
+        .with_prefix("/static")
+        .resolve(&req, Assets::get)
+        .use_compression(Compress::Always)
+}
+
+async fn site(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new()
+        .with_prefix("/site")
+        .with_clean_urls(true)
+        .with_trailing_slash_redirect(true)
+        .resolve(&req, Site::get)
+}
+
+async fn brotli_assets(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new()
+        .with_prefix("/br")
+        .resolve(&req, Assets::get)
+        .use_compression(Compress::Always)
+        .with_forced_encoding(Encoding::Br)
+}
+
+fn make_app() -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Response = ServiceResponse<impl MessageBody>,
+        Config = (),
+        InitError = (),
+        Error = actix_web::Error,
+    >,
+> {
+    App::new()
+        .service(web::scope("/static").default_service(web::to(assets)))
+        .service(web::scope("/site").default_service(web::to(site)))
+        .service(web::scope("/br").default_service(web::to(brotli_assets)))
+}
+
+fn style_css() -> Vec<u8> {
+    AsRef::<[u8]>::as_ref(&Assets::get("style.css").unwrap().data()).to_vec()
+}
+
+#[actix_web::test]
+async fn serves_files_and_index() {
+    let app = test::init_service(make_app()).await;
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/css");
+    assert_eq!(resp.headers().get("Accept-Ranges").unwrap(), "bytes");
+    assert!(resp.headers().get("ETag").is_some());
+    assert!(resp.headers().get("Last-Modified").is_some());
+    assert_eq!(test::read_body(resp).await, style_css());
+
+    let req = test::TestRequest::get().uri("/static/").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/html");
+}
+
+#[actix_web::test]
+async fn etag_round_trip_is_not_modified() {
+    let app = test::init_service(make_app()).await;
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    let last_modified = resp.headers().get("Last-Modified").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("If-None-Match", etag.clone()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 304);
+    assert_eq!(resp.headers().get("ETag").unwrap(), &etag);
+    assert!(test::read_body(resp).await.is_empty());
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("If-Modified-Since", last_modified))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn wildcard_if_none_match_is_not_modified() {
+    let app = test::init_service(make_app()).await;
+
+    for method in [Method::GET, Method::HEAD] {
+        let req = test::TestRequest::default()
+            .method(method)
+            .uri("/static/style.css")
+            .append_header(("If-None-Match", "*"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 304);
+        assert!(resp.headers().get("ETag").is_some());
+    }
+
+    let req = test::TestRequest::get()
+        .uri("/static/missing.css")
+        .append_header(("If-None-Match", "*"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn gzip_is_negotiated() {
+    let app = test::init_service(make_app()).await;
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Encoding");
+    let body = test::read_body(resp).await;
+    let mut decompressed = Vec::new();
+    GzDecoder::new(&body[..])
+        .read_to_end(&mut decompressed)
+        .unwrap();
+    assert_eq!(decompressed, style_css());
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("Accept-Encoding", "identity"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn forced_encoding_skips_negotiation() {
+    let app = test::init_service(make_app()).await;
+
+    let req = test::TestRequest::get().uri("/br/style.css").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "br");
+}
+
+#[actix_web::test]
+async fn ranges_are_served() {
+    let app = test::init_service(make_app()).await;
+    let data = style_css();
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("Range", "bytes=0-9"))
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 206);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert_eq!(
+        resp.headers().get("Content-Range").unwrap(),
+        format!("bytes 0-9/{}", data.len()).as_str()
+    );
+    assert_eq!(test::read_body(resp).await, &data[..10]);
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("Range", "bytes=0-1, 5-6"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 206);
+    let content_type = resp.headers().get("Content-Type").unwrap();
+    assert!(content_type
+        .to_str()
+        .unwrap()
+        .starts_with("multipart/byteranges; boundary="));
+
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .append_header(("Range", "bytes=100000-"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 416);
+}
+
+#[actix_web::test]
+async fn head_has_headers_but_no_body() {
+    let app = test::init_service(make_app()).await;
+
+    let req = test::TestRequest::default()
+        .method(Method::HEAD)
+        .uri("/static/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/css");
+    assert!(test::read_body(resp).await.is_empty());
+}
+
+#[actix_web::test]
+async fn missing_files_are_not_found() {
+    let app = test::init_service(make_app()).await;
+
+    for uri in ["/static/missing.css", "/site/missing", "/br/missing.css"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 404, "{uri}");
+        assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+    }
+}
+
+#[actix_web::test]
+async fn site_paths_are_resolved() {
+    let app = test::init_service(make_app()).await;
+
+    let req = test::TestRequest::get().uri("/site/about").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(std::str::from_utf8(&test::read_body(resp).await)
+        .unwrap()
+        .contains("<title>About</title>"));
+
+    let req = test::TestRequest::get().uri("/site/docs?v=1").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 301);
+    assert_eq!(resp.headers().get("Location").unwrap(), "/site/docs/?v=1");
+
+    let req = test::TestRequest::get().uri("/site/docs/").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/html");
+}
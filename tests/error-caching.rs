@@ -0,0 +1,59 @@
+use actix_web::{http::StatusCode, test, Responder};
+use actix_web_rust_embed_responder::{EmbedResponse, EmbedableFileResponse, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn not_found_is_not_stored() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp: EmbedResponse<EmbedableFileResponse> = EmbedREFW::get("missing.html").into_response();
+    let resp = resp
+        .with_cache_control("public, max-age=3600")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+}
+
+#[actix_web::test]
+async fn error_pages_are_not_stored() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_status(StatusCode::SERVICE_UNAVAILABLE)
+        .with_cache_control("public, max-age=3600")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 503);
+    let values: Vec<_> = resp.headers().get_all("Cache-Control").collect();
+    assert_eq!(values, ["no-store"]);
+}
+
+#[actix_web::test]
+async fn error_caching_can_be_turned_off() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_status(StatusCode::NOT_FOUND)
+        .with_cache_control("public, max-age=60")
+        .with_no_cache_for_errors(false)
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=60"
+    );
+}
+
+#[actix_web::test]
+async fn successful_responses_keep_their_cache_control() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_cache_control("public, max-age=3600")
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=3600"
+    );
+}
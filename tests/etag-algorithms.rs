@@ -0,0 +1,206 @@
+use std::io::Read;
+
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{Compress, EtagAlgorithm, IntoResponse, MemoryFile};
+
+fn hello() -> MemoryFile {
+    MemoryFile::new(&b"hello world"[..], "hello-v1")
+}
+
+#[cfg(feature = "etag-sha1")]
+#[actix_web::test]
+async fn sha1_etag_is_stable_and_quoted() {
+    for _ in 0..2 {
+        let req = test::TestRequest::get().to_http_request();
+        let resp = hello()
+            .into_response()
+            .with_etag_algorithm(EtagAlgorithm::Sha1)
+            .respond_to(&req);
+        assert_eq!(
+            resp.headers().get("ETag").unwrap(),
+            "\"2aae6c35c94fcfb415dbe95f408b9ce91ee846ed\""
+        );
+    }
+}
+
+#[cfg(feature = "etag-blake3")]
+#[actix_web::test]
+async fn blake3_etag_is_stable_and_quoted() {
+    for _ in 0..2 {
+        let req = test::TestRequest::get().to_http_request();
+        let resp = hello()
+            .into_response()
+            .with_etag_algorithm(EtagAlgorithm::Blake3)
+            .respond_to(&req);
+        assert_eq!(
+            resp.headers().get("ETag").unwrap(),
+            "\"d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24\""
+        );
+    }
+}
+
+#[actix_web::test]
+async fn conditional_requests_use_the_selected_etag() {
+    let req = test::TestRequest::get()
+        .append_header((
+            "If-None-Match",
+            "\"b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9\"",
+        ))
+        .to_http_request();
+    let resp = hello()
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Sha256Hex)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+
+    // The upstream ETag no longer matches once another algorithm is selected.
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"hello-v1\""))
+        .to_http_request();
+    let resp = hello()
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Sha256Hex)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(rust_embed_for_web::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+const STYLE_SHA256_HEX: &str =
+    "\"0d666e85a48ab7ec7a2e31eb24af169eab87485d638fee0e3a7c179f1dbb24a4\"";
+
+#[actix_web::test]
+async fn sha256_hex_etag_is_the_same_for_both_backends() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedRE::get("style.css")
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Sha256Hex)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("ETag").unwrap(), STYLE_SHA256_HEX);
+
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Sha256Hex)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("ETag").unwrap(), STYLE_SHA256_HEX);
+}
+
+#[actix_web::test]
+async fn sha256_base64_etag_matches_rust_embed() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Sha256Base64)
+        .respond_to(&req);
+    let upstream = EmbedRE::get("style.css").into_response().respond_to(&req);
+    assert_eq!(
+        resp.headers().get("ETag").unwrap(),
+        upstream.headers().get("ETag").unwrap()
+    );
+}
+
+#[actix_web::test]
+async fn crc32_etag_is_weak_and_matches_if_none_match() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = hello()
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Crc32)
+        .respond_to(&req);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    assert_eq!(etag, "W/\"b-0d4a1185\"");
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = hello()
+        .into_response()
+        .with_etag_algorithm(EtagAlgorithm::Crc32)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn files_without_an_etag_get_a_weak_size_and_mtime_etag() {
+    let file = || MemoryFile::new(&b"hello world"[..], "").with_last_modified_timestamp(1445412480);
+    let req = test::TestRequest::get().to_http_request();
+    let resp = file().into_response().respond_to(&req);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    assert_eq!(etag, "W/\"11-1445412480\"");
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = file().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn fallback_etag_uses_the_last_modified_override() {
+    let file = || MemoryFile::new(&b"hello world"[..], "").with_last_modified_timestamp(1445412480);
+    let req = test::TestRequest::get().to_http_request();
+    let resp = file()
+        .into_response()
+        .with_last_modified_timestamp(1700000000)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("ETag").unwrap(), "W/\"11-1700000000\"");
+    assert_eq!(
+        resp.headers().get("Last-Modified").unwrap(),
+        "Tue, 14 Nov 2023 22:13:20 GMT"
+    );
+
+    // The ETag from before the override no longer matches.
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "W/\"11-1445412480\""))
+        .to_http_request();
+    let resp = file()
+        .into_response()
+        .with_last_modified_timestamp(1700000000)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn files_without_an_etag_are_compressed_separately() {
+    for contents in ["first file", "second file"] {
+        let req = test::TestRequest::get()
+            .append_header(("Accept-Encoding", "gzip"))
+            .to_http_request();
+        let resp = MemoryFile::new(contents.as_bytes().to_vec(), "")
+            .into_response()
+            .use_compression(Compress::Always)
+            .respond_to(&req);
+        let compressed = body::to_bytes(resp.into_body()).await.unwrap();
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, contents);
+    }
+}
+
+#[actix_web::test]
+async fn empty_if_none_match_does_not_match_blank_etags() {
+    for upstream in ["", "  ", "\" \""] {
+        let file = || MemoryFile::new(&b"hello world"[..], upstream);
+        let req = test::TestRequest::get()
+            .append_header(("If-None-Match", "\"\""))
+            .to_http_request();
+        let resp = file().into_response().respond_to(&req);
+        assert_eq!(resp.status(), 200, "{upstream:?}");
+        // Blank ETags are replaced with the weak fallback, so this never
+        // matches an empty `If-None-Match`.
+        assert_eq!(
+            resp.headers().get("ETag").unwrap(),
+            "W/\"b-0d4a1185\"",
+            "{upstream:?}"
+        );
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(body, "hello world");
+    }
+}
@@ -0,0 +1,80 @@
+use actix_web::{test, HttpRequest, Responder};
+use actix_web_rust_embed_responder::{Compress, EtagMode, IntoResponse};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn etag() -> String {
+    EmbedREFW::get("style.css").unwrap().etag().to_string()
+}
+
+fn request(gzip: bool, if_none_match: Option<&str>) -> HttpRequest {
+    let mut req = test::TestRequest::get();
+    if gzip {
+        req = req.append_header(("Accept-Encoding", "gzip"));
+    }
+    if let Some(value) = if_none_match {
+        req = req.append_header(("If-None-Match", value.to_string()));
+    }
+    req.to_http_request()
+}
+
+fn respond(mode: EtagMode, req: &HttpRequest) -> actix_web::HttpResponse {
+    EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_etag_mode(mode)
+        .respond_to(req)
+}
+
+fn response_etag(resp: &actix_web::HttpResponse) -> Option<String> {
+    resp.headers()
+        .get("ETag")
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+#[actix_web::test]
+async fn shared_etag() {
+    let gzip = respond(EtagMode::Shared, &request(true, None));
+    assert_eq!(gzip.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(response_etag(&gzip), Some(etag()));
+    let identity = respond(EtagMode::Shared, &request(false, None));
+    assert_eq!(response_etag(&identity), Some(etag()));
+
+    let resp = respond(EtagMode::Shared, &request(true, Some(&etag())));
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn per_encoding_etag() {
+    let gzip_etag = format!("{}-gzip\"", etag().trim_end_matches('"'));
+    let gzip = respond(EtagMode::PerEncoding, &request(true, None));
+    assert_eq!(response_etag(&gzip), Some(gzip_etag.clone()));
+    let identity = respond(EtagMode::PerEncoding, &request(false, None));
+    assert_eq!(response_etag(&identity), Some(etag()));
+
+    let resp = respond(EtagMode::PerEncoding, &request(true, Some(&gzip_etag)));
+    assert_eq!(resp.status(), 304);
+    assert_eq!(response_etag(&resp), Some(gzip_etag.clone()));
+    // A copy with another encoding is not the same version of the file.
+    let resp = respond(EtagMode::PerEncoding, &request(false, Some(&gzip_etag)));
+    assert_eq!(resp.status(), 200);
+    let resp = respond(EtagMode::PerEncoding, &request(true, Some(&etag())));
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn no_etag_on_compressed() {
+    let gzip = respond(EtagMode::NoneOnCompressed, &request(true, None));
+    assert_eq!(gzip.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(response_etag(&gzip), None);
+    let identity = respond(EtagMode::NoneOnCompressed, &request(false, None));
+    assert_eq!(response_etag(&identity), Some(etag()));
+
+    let resp = respond(EtagMode::NoneOnCompressed, &request(true, Some(&etag())));
+    assert_eq!(resp.status(), 200);
+    let resp = respond(EtagMode::NoneOnCompressed, &request(false, Some(&etag())));
+    assert_eq!(resp.status(), 304);
+}
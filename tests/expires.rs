@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{FixedClock, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn expires_is_a_future_http_date() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_cache_control("public, max-age=3600")
+        .with_expires(Duration::from_secs(3600))
+        .respond_to(&req);
+    let expires = resp.headers().get("Expires").unwrap().to_str().unwrap();
+    assert!(expires.ends_with(" GMT"), "{expires}");
+    let expires = chrono::DateTime::parse_from_rfc2822(expires).unwrap();
+    let from_now = expires.timestamp() - chrono::Utc::now().timestamp();
+    assert!((3590..=3600).contains(&from_now), "{from_now}");
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=3600"
+    );
+}
+
+#[actix_web::test]
+async fn expires_is_not_sent_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert!(resp.headers().get("Expires").is_none());
+}
+
+#[actix_web::test]
+async fn expires_uses_the_clock() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_expires(Duration::from_secs(3600))
+        // Wed, 21 Oct 2015 07:28:00 GMT
+        .with_clock(FixedClock(1445412480))
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Expires").unwrap(),
+        "Wed, 21 Oct 2015 08:28:00 GMT"
+    );
+}
+
+#[actix_web::test]
+async fn huge_expires_durations_do_not_overflow() {
+    let req = test::TestRequest::get().to_http_request();
+    for duration in [Duration::MAX, Duration::from_secs(i64::MAX as u64)] {
+        let resp = EmbedREFW::get("style.css")
+            .into_response()
+            .with_expires(duration)
+            .with_clock(FixedClock(1445412480))
+            .respond_to(&req);
+        assert_eq!(resp.status(), 200);
+        assert!(resp.headers().get("Expires").is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use flate2::read::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+use std::io::Read;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn gzip_is_sent_without_accept_encoding() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_force_gzip(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    let mut decompressed = Vec::new();
+    GzDecoder::new(&body[..])
+        .read_to_end(&mut decompressed)
+        .unwrap();
+    let file = EmbedREFW::get("index.html").unwrap();
+    assert_eq!(&decompressed[..], AsRef::<[u8]>::as_ref(&file.data()));
+}
+
+#[actix_web::test]
+async fn gzip_is_not_forced_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn forced_gzip_follows_the_compression_option() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Never)
+        .with_force_gzip(true)
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "br"))
+        .to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_force_gzip(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "br");
+}
@@ -0,0 +1,69 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{Compress, Encoding, IntoResponse};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+use std::io::Read;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn index_html() -> Vec<u8> {
+    AsRef::<[u8]>::as_ref(&EmbedREFW::get("index.html").unwrap().data()).to_vec()
+}
+
+#[actix_web::test]
+async fn forced_brotli_ignores_accept_encoding() {
+    for accept_encoding in [None, Some("gzip"), Some("identity")] {
+        let mut req = test::TestRequest::get();
+        if let Some(value) = accept_encoding {
+            req = req.append_header(("Accept-Encoding", value));
+        }
+        let resp = EmbedREFW::get("index.html")
+            .into_response()
+            .use_compression(Compress::Always)
+            .with_forced_encoding(Encoding::Br)
+            .respond_to(&req.to_http_request());
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers().get("Content-Encoding").unwrap(),
+            "br",
+            "{accept_encoding:?}"
+        );
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let mut decompressed = Vec::new();
+        brotli::Decompressor::new(&body[..], 4096)
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, index_html());
+    }
+}
+
+#[actix_web::test]
+async fn forced_identity_is_uncompressed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "br, gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_forced_encoding(Encoding::Identity)
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, index_html());
+}
+
+#[actix_web::test]
+async fn unavailable_encoding_falls_back_to_identity() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .use_compression(Compress::Never)
+        .with_forced_encoding(Encoding::Gzip)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
@@ -0,0 +1,74 @@
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::{body, http::Method, test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn head() -> test::TestRequest {
+    test::TestRequest::default()
+        .method(Method::HEAD)
+        .append_header(("Accept-Encoding", "gzip"))
+}
+
+#[actix_web::test]
+async fn head_with_matching_etag_is_not_modified() {
+    let etag = EmbedREFW::get("style.css").into_response().etag().unwrap();
+    let req = head()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert!(resp.headers().get("Content-Type").is_none());
+}
+
+#[actix_web::test]
+async fn head_without_a_match_has_the_get_headers() {
+    let get = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .append_header(("If-None-Match", "\"other\""))
+        .to_http_request();
+    let get = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&get);
+    let get_headers = get.headers().clone();
+    let get_body = body::to_bytes(get.into_body()).await.unwrap();
+
+    let req = head()
+        .append_header(("If-None-Match", "\"other\""))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    for (name, value) in get_headers.iter() {
+        assert_eq!(resp.headers().get(name), Some(value), "{name}");
+    }
+    assert_eq!(resp.body().size(), BodySize::Sized(get_body.len() as u64));
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert!(body.is_empty());
+}
+
+#[actix_web::test]
+async fn head_without_compression_has_the_file_length() {
+    let len = EmbedREFW::get("style.css").unwrap().data().len();
+    let req = test::TestRequest::default()
+        .method(Method::HEAD)
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert_eq!(resp.body().size(), BodySize::Sized(len as u64));
+}
@@ -0,0 +1,67 @@
+use actix_web::{
+    body::{self, BodySize, MessageBody},
+    http::Version,
+    test, Responder,
+};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn etag() -> String {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    resp.headers()
+        .get("ETag")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[actix_web::test]
+async fn http10_clients_get_the_full_file() {
+    let req = test::TestRequest::get()
+        .version(Version::HTTP_10)
+        .append_header(("If-None-Match", etag()))
+        .append_header(("Range", "bytes=0-9"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_http10_compat(true)
+        .with_auto_streaming(1)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Accept-Ranges").is_none());
+    let len = EmbedREFW::get("style.css")
+        .into_response()
+        .content_length()
+        .unwrap();
+    assert_eq!(resp.body().size(), BodySize::Sized(len as u64));
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body.len(), len);
+}
+
+#[actix_web::test]
+async fn http10_clients_get_not_modified_without_the_flag() {
+    let req = test::TestRequest::get()
+        .version(Version::HTTP_10)
+        .append_header(("If-None-Match", etag()))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn http11_clients_are_unaffected() {
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag()))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_http10_compat(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
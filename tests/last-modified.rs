@@ -0,0 +1,162 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{EmbedRespondable, EmbedResponse, IntoResponse, MemoryFile};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn last_modified_can_be_overridden() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_last_modified_timestamp(1445412480)
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Last-Modified").unwrap(),
+        "Wed, 21 Oct 2015 07:28:00 GMT"
+    );
+}
+
+#[actix_web::test]
+async fn overridden_last_modified_is_used_for_conditionals() {
+    let req = test::TestRequest::get()
+        .append_header(("If-Modified-Since", "Wed, 21 Oct 2015 07:28:00 GMT"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_last_modified_timestamp(1445412480)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_last_modified_timestamp(1445412481)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn last_modified_can_be_reformatted() {
+    let req = test::TestRequest::get().to_http_request();
+    // rust-embed-for-web formats its dates like `Wed, 21 Oct 2015 07:28:00 +0000`.
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    let upstream = resp.headers().get("Last-Modified").unwrap();
+    assert!(upstream.to_str().unwrap().ends_with(" +0000"));
+
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_reformatted_last_modified(true)
+        .respond_to(&req);
+    let reformatted = resp.headers().get("Last-Modified").unwrap();
+    assert!(reformatted.to_str().unwrap().ends_with(" GMT"));
+    let parse = |date: &actix_web::http::header::HeaderValue| {
+        chrono::DateTime::parse_from_rfc2822(date.to_str().unwrap()).unwrap()
+    };
+    assert_eq!(parse(reformatted), parse(upstream));
+}
+
+#[actix_web::test]
+async fn rust_embed_files_use_http_dates() {
+    let req = test::TestRequest::get().to_http_request();
+    let file = EmbedRE::get("style.css").unwrap();
+    let timestamp = file.last_modified_timestamp().unwrap();
+    let resp = EmbedRE::get("style.css").into_response().respond_to(&req);
+    let last_modified = resp.headers().get("Last-Modified").unwrap().clone();
+    assert!(last_modified.to_str().unwrap().ends_with(" GMT"));
+    let date = chrono::DateTime::parse_from_rfc2822(last_modified.to_str().unwrap()).unwrap();
+    assert_eq!(date.timestamp(), timestamp);
+
+    let req = test::TestRequest::get()
+        .append_header(("If-Modified-Since", last_modified))
+        .to_http_request();
+    let resp = EmbedRE::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn memory_files_use_http_dates() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = MemoryFile::new(&b"hello world"[..], "hello")
+        .with_last_modified_timestamp(1445412480)
+        .into_response()
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Last-Modified").unwrap(),
+        "Wed, 21 Oct 2015 07:28:00 GMT"
+    );
+}
+
+/// A file that knows when it was modified, but has no formatted date for it.
+struct TimestampOnly;
+
+impl EmbedRespondable for TimestampOnly {
+    type Data = &'static [u8];
+    type DataGzip = &'static [u8];
+    type DataBr = &'static [u8];
+    type MimeType = &'static str;
+    type ETag = &'static str;
+    type LastModified = &'static str;
+
+    fn data(&self) -> Self::Data {
+        b"hello world"
+    }
+
+    fn data_gzip(&self) -> Option<Self::DataGzip> {
+        None
+    }
+
+    fn data_br(&self) -> Option<Self::DataBr> {
+        None
+    }
+
+    fn last_modified_timestamp(&self) -> Option<i64> {
+        // Wed, 21 Oct 2015 07:28:00 GMT
+        Some(1445412480)
+    }
+
+    fn last_modified(&self) -> Option<Self::LastModified> {
+        None
+    }
+
+    fn etag(&self) -> Self::ETag {
+        "\"hello\""
+    }
+
+    fn mime_type(&self) -> Option<Self::MimeType> {
+        Some("text/plain")
+    }
+}
+
+#[actix_web::test]
+async fn timestamp_is_enough_for_date_conditionals() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedResponse::new(Some(TimestampOnly)).respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Last-Modified").unwrap(),
+        "Wed, 21 Oct 2015 07:28:00 GMT"
+    );
+
+    for (if_unmodified_since, status) in [
+        ("Wed, 21 Oct 2015 07:28:00 GMT", 200),
+        ("Tue, 20 Oct 2015 07:28:00 GMT", 412),
+    ] {
+        let req = test::TestRequest::get()
+            .append_header(("If-Unmodified-Since", if_unmodified_since))
+            .to_http_request();
+        let resp = EmbedResponse::new(Some(TimestampOnly)).respond_to(&req);
+        assert_eq!(resp.status(), status, "{if_unmodified_since}");
+    }
+
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-4"))
+        .append_header(("If-Range", "Wed, 21 Oct 2015 07:28:00 GMT"))
+        .to_http_request();
+    let resp = EmbedResponse::new(Some(TimestampOnly)).respond_to(&req);
+    assert_eq!(resp.status(), 206);
+}
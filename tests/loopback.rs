@@ -0,0 +1,46 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn request_from(peer: &str) -> actix_web::HttpRequest {
+    test::TestRequest::get()
+        .peer_addr(peer.parse().unwrap())
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request()
+}
+
+#[actix_web::test]
+async fn loopback_clients_get_uncompressed_files() {
+    for peer in ["127.0.0.1:51234", "[::1]:51234"] {
+        let resp = EmbedREFW::get("style.css")
+            .into_response()
+            .use_compression(Compress::Always)
+            .with_skip_compression_on_loopback(true)
+            .respond_to(&request_from(peer));
+        assert_eq!(resp.status(), 200);
+        assert!(resp.headers().get("Content-Encoding").is_none());
+    }
+}
+
+#[actix_web::test]
+async fn remote_clients_still_get_compressed_files() {
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_skip_compression_on_loopback(true)
+        .respond_to(&request_from("203.0.113.7:51234"));
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+}
+
+#[actix_web::test]
+async fn loopback_clients_are_compressed_by_default() {
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&request_from("127.0.0.1:51234"));
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+}
@@ -0,0 +1,89 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse, LinkHeader, MANAGED_HEADERS};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn response(managed: Option<&[&str]>) -> actix_web::HttpResponse {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_expires(std::time::Duration::from_secs(60))
+        .with_attachment()
+        .with_early_hints(vec![LinkHeader::preload("/font.woff2")])
+        .with_timing_allow_origin("*")
+        .with_nel(r#"{"report_to":"default","max_age":60}"#)
+        .with_report_to(r#"{"group":"default","max_age":60,"endpoints":[]}"#)
+        .with_repr_digest(true)
+        .with_last_modified_timestamp(1445412480);
+    match managed {
+        Some(managed) => resp.with_managed_headers(managed).respond_to(&req),
+        None => resp.respond_to(&req),
+    }
+}
+
+#[actix_web::test]
+async fn all_managed_headers_are_set_by_default() {
+    let resp = response(None);
+    for name in MANAGED_HEADERS {
+        assert!(resp.headers().contains_key(*name), "{name}");
+    }
+}
+
+#[actix_web::test]
+async fn only_the_managed_subset_is_set() {
+    let resp = response(Some(&["etag", "Content-Type"]));
+    assert_eq!(resp.status(), 200);
+    for name in MANAGED_HEADERS {
+        let expected = ["ETag", "Content-Type"].contains(name);
+        assert_eq!(resp.headers().contains_key(*name), expected, "{name}");
+    }
+}
+
+#[actix_web::test]
+async fn conditionals_still_work_without_the_etag_header() {
+    let etag = response(None).headers().get("ETag").unwrap().clone();
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_managed_headers(&[])
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert!(resp.headers().get("ETag").is_none());
+    assert!(resp.headers().get("Cache-Control").is_none());
+}
+
+#[actix_web::test]
+async fn multipart_content_type_is_always_set() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-1, 4-5"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_managed_headers(&["ETag"])
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    let content_type = resp.headers().get("Content-Type").unwrap();
+    assert!(content_type
+        .to_str()
+        .unwrap()
+        .starts_with("multipart/byteranges; boundary="));
+    assert!(resp.headers().get("Cache-Control").is_none());
+}
+
+#[actix_web::test]
+async fn other_responses_keep_their_headers() {
+    let req = test::TestRequest::post().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_method_not_allowed_body("not allowed", "text/plain")
+        .with_managed_headers(&[])
+        .respond_to(&req);
+    assert_eq!(resp.status(), 405);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/plain");
+}
@@ -0,0 +1,121 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse, MemoryFile};
+use std::{borrow::Cow, io::Read};
+
+fn generated() -> MemoryFile {
+    let data: Vec<u8> = format!("{{\"version\":{}}}", 3).into_bytes();
+    MemoryFile::new(data, "manifest-v3")
+        .with_mime_type("application/json")
+        .with_last_modified_timestamp(1_700_000_000)
+}
+
+#[actix_web::test]
+async fn serves_runtime_data() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = generated().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("ETag").unwrap(), "\"manifest-v3\"");
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert!(resp.headers().get("Last-Modified").is_some());
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, r#"{"version":3}"#);
+}
+
+#[actix_web::test]
+async fn runtime_data_can_be_not_modified() {
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"manifest-v3\""))
+        .to_http_request();
+    let resp = generated().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn serves_owned_cow_data() {
+    let data: Cow<'static, [u8]> = Cow::Owned(b"generated at runtime".to_vec());
+    let file = || {
+        MemoryFile::new(data.clone(), "runtime-v1")
+            .with_mime_type("text/plain")
+            .with_last_modified_timestamp(1_700_000_000)
+    };
+    let req = test::TestRequest::get().to_http_request();
+    let resp = file().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, "generated at runtime");
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"runtime-v1\""))
+        .to_http_request();
+    let resp = file().into_response().respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn quoted_etags_are_kept() {
+    let resp = MemoryFile::new(&b"x"[..], "\"quoted\"").into_response();
+    assert_eq!(resp.etag().as_deref(), Some("\"quoted\""));
+}
+
+#[actix_web::test]
+async fn bytes_get_an_etag_and_mime_type() {
+    let bytes = || br#"{"version":4}"#.to_vec();
+    let req = test::TestRequest::get().to_http_request();
+    let resp = MemoryFile::from_bytes(bytes(), "/manifest.json")
+        .into_response()
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert!(resp.headers().get("Last-Modified").is_some());
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    // The same bytes get the same ETag, even when they are generated again.
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = MemoryFile::from_bytes(bytes(), "/manifest.json")
+        .into_response()
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn bytes_can_be_a_cow() {
+    let borrowed = MemoryFile::from_bytes(Cow::Borrowed(&b"manifest"[..]), "/manifest.json");
+    let owned = MemoryFile::from_bytes(Cow::Owned(b"manifest".to_vec()), "/manifest.json");
+    let req = test::TestRequest::get().to_http_request();
+    let borrowed = borrowed.into_response().respond_to(&req);
+    let owned = owned.into_response().respond_to(&req);
+    assert_eq!(borrowed.headers().get("ETag"), owned.headers().get("ETag"));
+    let body = body::to_bytes(owned.into_body()).await.unwrap();
+    assert_eq!(body, "manifest");
+}
+
+#[actix_web::test]
+async fn memory_files_are_not_cached() {
+    // Both versions have the same ETag, so a cache keyed by it would send the
+    // first version for both.
+    for contents in ["first version", "second version"] {
+        let req = test::TestRequest::get()
+            .append_header(("Accept-Encoding", "gzip"))
+            .to_http_request();
+        let resp = MemoryFile::new(contents.as_bytes().to_vec(), "manifest")
+            .with_mime_type("text/plain")
+            .into_response()
+            .use_compression(Compress::Always)
+            .respond_to(&req);
+        assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+        let compressed = body::to_bytes(resp.into_body()).await.unwrap();
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, contents);
+    }
+}
@@ -0,0 +1,36 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn other_methods_are_rejected() {
+    let req = test::TestRequest::post().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .respond_to(&req);
+    assert_eq!(resp.status(), 405);
+    assert_eq!(resp.headers().get("Allow").unwrap(), "GET, HEAD");
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert!(body.is_empty());
+}
+
+#[actix_web::test]
+async fn method_not_allowed_body_is_sent() {
+    let req = test::TestRequest::post().to_http_request();
+    let resp = EmbedREFW::get("index.html")
+        .into_response()
+        .with_method_not_allowed_body(r#"{"error":"method not allowed"}"#, "application/json")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 405);
+    assert_eq!(resp.headers().get("Allow").unwrap(), "GET, HEAD");
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, r#"{"error":"method not allowed"}"#);
+}
@@ -0,0 +1,90 @@
+use actix_http::body::MessageBody;
+use actix_web::test;
+use actix_web::{
+    dev::{ServiceFactory, ServiceRequest, ServiceResponse},
+    route, web, App,
+};
+use actix_web_rust_embed_responder::{
+    EmbedResponse, EmbedableFileResponse, IntoResponse, MimeRegistry,
+};
+use rust_embed::EmbeddedFile;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "tests/assets/wasm/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/wasm/"]
+struct EmbedREFW;
+
+#[route("/re/{path:.*}", method = "GET")]
+async fn re_handler(path: web::Path<String>) -> EmbedResponse<EmbeddedFile> {
+    EmbedRE::get(&path).into_response()
+}
+
+#[route("/refw/{path:.*}", method = "GET")]
+async fn refw_handler(path: web::Path<String>) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedREFW::get(&path).into_response()
+}
+
+#[route("/override/{path:.*}", method = "GET")]
+async fn override_handler(path: web::Path<String>) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedREFW::get(&path)
+        .into_response()
+        .with_content_type("application/octet-stream")
+}
+
+fn make_app() -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Response = ServiceResponse<impl MessageBody>,
+        Config = (),
+        InitError = (),
+        Error = actix_web::Error,
+    >,
+> {
+    App::new()
+        .app_data(
+            MimeRegistry::new()
+                .with_mapping("wasm", "application/wasm")
+                .with_mapping("custom", "application/x-custom"),
+        )
+        .service(re_handler)
+        .service(refw_handler)
+        .service(override_handler)
+}
+
+async fn content_type(uri: &str) -> Option<String> {
+    let app = test::init_service(make_app()).await;
+    let req = test::TestRequest::get().uri(uri).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    resp.headers()
+        .get("Content-Type")
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+#[actix_web::test]
+async fn registered_types_are_used() {
+    assert_eq!(
+        content_type("/re/module.wasm").await.as_deref(),
+        Some("application/wasm")
+    );
+    assert_eq!(
+        content_type("/refw/module.wasm").await.as_deref(),
+        Some("application/wasm")
+    );
+    assert_eq!(
+        content_type("/refw/data.custom").await.as_deref(),
+        Some("application/x-custom")
+    );
+}
+
+#[actix_web::test]
+async fn content_type_on_the_response_wins() {
+    assert_eq!(
+        content_type("/override/module.wasm").await.as_deref(),
+        Some("application/octet-stream")
+    );
+}
@@ -0,0 +1,48 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+const NEL: &str = r#"{"report_to":"default","max_age":2592000}"#;
+const REPORT_TO: &str =
+    r#"{"group":"default","max_age":2592000,"endpoints":[{"url":"https://example.com/reports"}]}"#;
+
+#[actix_web::test]
+async fn nel_and_report_to_are_sent() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_nel(NEL)
+        .with_report_to(REPORT_TO.to_string())
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("NEL").unwrap(), NEL);
+    assert_eq!(resp.headers().get("Report-To").unwrap(), REPORT_TO);
+
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("missing.css")
+        .into_response()
+        .with_nel(NEL)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("NEL").unwrap(), NEL);
+}
+
+#[actix_web::test]
+async fn nel_and_report_to_are_not_sent_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert!(resp.headers().get("NEL").is_none());
+    assert!(resp.headers().get("Report-To").is_none());
+
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_nel(" ")
+        .with_report_to("")
+        .respond_to(&req);
+    assert!(resp.headers().get("NEL").is_none());
+    assert!(resp.headers().get("Report-To").is_none());
+}
@@ -0,0 +1,67 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn no_transform_composes_with_cache_control() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_cache_control("public, max-age=3600")
+        .with_no_transform(true)
+        .respond_to(&req);
+    let values: Vec<_> = resp.headers().get_all("Cache-Control").collect();
+    assert_eq!(values, ["public, max-age=3600, no-transform"]);
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_cache_control("public, max-age=3600")
+        .with_no_transform(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=3600, no-transform"
+    );
+}
+
+#[actix_web::test]
+async fn no_transform_with_the_default_cache_control() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_no_transform(true)
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "no-cache, no-transform"
+    );
+
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_no_transform(false)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-cache");
+}
+
+#[actix_web::test]
+async fn no_transform_is_not_repeated() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_cache_control("public, No-Transform, max-age=3600")
+        .with_no_transform(true)
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, No-Transform, max-age=3600"
+    );
+}
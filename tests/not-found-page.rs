@@ -0,0 +1,112 @@
+use actix_http::body::MessageBody;
+use actix_web::test;
+use actix_web::{
+    dev::{ServiceFactory, ServiceRequest, ServiceResponse},
+    route, App, HttpRequest,
+};
+use actix_web_rust_embed_responder::{
+    Compress, EmbedResponse, EmbedableFileResponse, IntoResponse, NotFoundPage,
+};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct Assets;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/errors/"]
+struct Errors;
+
+#[route("/{path:.*}", method = "GET", method = "HEAD")]
+async fn handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    match Assets::get(req.path().trim_start_matches('/')) {
+        Some(file) => file.into_response(),
+        None => NotFoundPage::new()
+            .resolve(&req, Errors::get)
+            .use_compression(Compress::Always),
+    }
+}
+
+fn make_app() -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Response = ServiceResponse<impl MessageBody>,
+        Config = (),
+        InitError = (),
+        Error = actix_web::Error,
+    >,
+> {
+    App::new().service(handler)
+}
+
+#[actix_web::test]
+async fn browsers_get_the_html_page() {
+    let app = test::init_service(make_app()).await;
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .append_header((
+            "Accept",
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/html");
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+    let body = test::read_body(resp).await;
+    assert!(String::from_utf8_lossy(&body).contains("Page not found"));
+}
+
+#[actix_web::test]
+async fn api_clients_get_the_json_page() {
+    let app = test::init_service(make_app()).await;
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .append_header(("Accept", "application/json"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "{\"error\":\"not found\"}\n");
+}
+
+#[actix_web::test]
+async fn no_accept_header_gets_the_html_page() {
+    let app = test::init_service(make_app()).await;
+    let req = test::TestRequest::get().uri("/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/html");
+}
+
+#[actix_web::test]
+async fn error_pages_can_be_compressed() {
+    let app = test::init_service(make_app()).await;
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+}
+
+#[actix_web::test]
+async fn error_pages_ignore_conditionals() {
+    let app = test::init_service(make_app()).await;
+    let req = test::TestRequest::get().uri("/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .append_header(("If-None-Match", etag))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
@@ -0,0 +1,41 @@
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn offload_header_replaces_the_body() {
+    let req = test::TestRequest::get()
+        .uri("/static/style.css")
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_offload_header("X-Accel-Redirect", |req| format!("/internal{}", req.path()))
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("X-Accel-Redirect").unwrap(),
+        "/internal/static/style.css"
+    );
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/css");
+    assert!(resp.headers().get("ETag").is_some());
+    assert_eq!(resp.body().size(), BodySize::Sized(0));
+}
+
+#[actix_web::test]
+async fn offloaded_files_can_be_revalidated() {
+    let etag = EmbedREFW::get("style.css").into_response().etag().unwrap();
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_offload_header("X-Sendfile", |_| "/var/www/style.css".to_string())
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert!(resp.headers().get("X-Sendfile").is_none());
+}
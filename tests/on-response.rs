@@ -0,0 +1,63 @@
+use std::sync::{Arc, Mutex};
+
+use actix_web::{http::StatusCode, test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse, ServeInfo};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn serve(req: &actix_web::HttpRequest, path: &str) -> ServeInfo {
+    let info = Arc::new(Mutex::new(None));
+    let saved = info.clone();
+    EmbedREFW::get(path)
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_on_response(move |info| *saved.lock().unwrap() = Some(info.clone()))
+        .respond_to(req);
+    let info = info.lock().unwrap().take();
+    info.expect("the callback was not called")
+}
+
+#[actix_web::test]
+async fn callback_gets_the_response_info() {
+    let req = test::TestRequest::get()
+        .uri("/style.css")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let info = serve(&req, "style.css");
+    assert_eq!(info.path, "/style.css");
+    assert_eq!(info.status, StatusCode::OK);
+    assert_eq!(info.encoding.as_deref(), Some("gzip"));
+    assert!(info.body_length.unwrap() > 0);
+
+    let req = test::TestRequest::get().uri("/style.css").to_http_request();
+    let info = serve(&req, "style.css");
+    assert_eq!(info.encoding, None);
+    assert_eq!(
+        info.body_length,
+        Some(EmbedREFW::get("style.css").unwrap().data().len() as u64)
+    );
+}
+
+#[actix_web::test]
+async fn callback_is_called_for_errors() {
+    let req = test::TestRequest::get()
+        .uri("/missing.css")
+        .to_http_request();
+    let info = serve(&req, "missing.css");
+    assert_eq!(info.status, StatusCode::NOT_FOUND);
+    assert_eq!(info.body_length, Some(0));
+}
+
+#[actix_web::test]
+async fn responses_with_callbacks_are_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let response = EmbedREFW::get("style.css")
+        .into_response()
+        .with_on_response(|_| {})
+        .with_offload_header("X-Accel-Redirect", |req| req.path().to_string());
+    assert_send(&response);
+}
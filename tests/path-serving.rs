@@ -0,0 +1,139 @@
+use actix_http::body::MessageBody;
+use actix_web::test;
+use actix_web::{
+    dev::{ServiceFactory, ServiceRequest, ServiceResponse},
+    web, App, HttpRequest, Responder,
+};
+use actix_web_rust_embed_responder::{EmbedPath, EmbedResponse, EmbedableFileResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+async fn root_handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new().resolve(&req, EmbedREFW::get)
+}
+
+async fn assets_handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new()
+        .with_prefix("/assets")
+        .resolve(&req, EmbedREFW::get)
+}
+
+async fn make_app() -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Response = ServiceResponse<impl MessageBody>,
+        Config = (),
+        InitError = (),
+        Error = actix_web::Error,
+    >,
+> {
+    App::new()
+        .service(web::scope("/assets").default_service(web::to(assets_handler)))
+        .default_service(web::to(root_handler))
+}
+
+#[actix_web::test]
+async fn serves_files_at_root_mount() {
+    let app = test::init_service(make_app().await).await;
+
+    let req = test::TestRequest::get().uri("/style.css").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let req = test::TestRequest::get().uri("/").to_request();
+    let resp = test::call_and_read_body(&app, req).await;
+    assert!(resp.starts_with(b"<!DOCTYPE html>"));
+}
+
+#[actix_web::test]
+async fn serves_files_at_prefix_mount() {
+    let app = test::init_service(make_app().await).await;
+
+    let req = test::TestRequest::get()
+        .uri("/assets/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    for uri in ["/assets", "/assets/"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let resp = test::call_and_read_body(&app, req).await;
+        assert!(resp.starts_with(b"<!DOCTYPE html>"));
+    }
+
+    let req = test::TestRequest::get()
+        .uri("/assets/does-not-exist.txt")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn paths_outside_prefix_are_not_found() {
+    let path = EmbedPath::new().with_prefix("/assets");
+
+    for uri in ["/style.css", "/assetsstyle.css"] {
+        let req = test::TestRequest::get().uri(uri).to_http_request();
+        let resp = path.resolve(&req, EmbedREFW::get).respond_to(&req);
+        assert_eq!(resp.status(), 404);
+    }
+}
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/encoded/"]
+struct EncodedEmbed;
+
+#[actix_web::test]
+async fn encoded_paths_are_decoded() {
+    let path = EmbedPath::new()
+        .with_clean_urls(true)
+        .with_trailing_slash_redirect(true);
+
+    for uri in [
+        "/my%20file.css",
+        "/men%C3%BC.html",
+        "/men%C3%BC",
+        "/d%6Fcs/",
+    ] {
+        let req = test::TestRequest::get().uri(uri).to_http_request();
+        let resp = path.resolve(&req, EncodedEmbed::get).respond_to(&req);
+        assert_eq!(resp.status(), 200, "{uri}");
+    }
+
+    let req = test::TestRequest::get().uri("/d%6Fcs").to_http_request();
+    let resp = path.resolve(&req, EncodedEmbed::get).respond_to(&req);
+    assert_eq!(resp.status(), 301);
+    assert_eq!(resp.headers().get("Location").unwrap(), "/d%6Fcs/");
+
+    for uri in ["/%FF.css", "/docs/%2E%2E/my%20file.css"] {
+        let req = test::TestRequest::get().uri(uri).to_http_request();
+        let resp = path.resolve(&req, EncodedEmbed::get).respond_to(&req);
+        assert_eq!(resp.status(), 404, "{uri}");
+    }
+}
+
+#[actix_web::test]
+async fn redirects_stay_on_the_same_host() {
+    let path = EmbedPath::new().with_trailing_slash_redirect(true);
+
+    for (uri, location) in [("//docs", "/docs/"), ("///docs?v=1", "/docs/?v=1")] {
+        let req = test::TestRequest::get().uri(uri).to_http_request();
+        let resp = path.resolve(&req, EncodedEmbed::get).respond_to(&req);
+        assert_eq!(resp.status(), 301, "{uri}");
+        assert_eq!(resp.headers().get("Location").unwrap(), location, "{uri}");
+    }
+
+    let path = path.with_prefix("/evil.example");
+    let req = test::TestRequest::get()
+        .uri("//evil.example/docs")
+        .to_http_request();
+    let resp = path.resolve(&req, EncodedEmbed::get).respond_to(&req);
+    assert_eq!(resp.status(), 301);
+    assert_eq!(
+        resp.headers().get("Location").unwrap(),
+        "/evil.example/docs/"
+    );
+}
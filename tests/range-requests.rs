@@ -0,0 +1,263 @@
+use actix_web::{
+    body::{self, BodySize, MessageBody},
+    http::Method,
+    test, Responder,
+};
+use actix_web_rust_embed_responder::{Compress, IntoResponse, RangeCompressionPolicy};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn style_css() -> Vec<u8> {
+    AsRef::<[u8]>::as_ref(&EmbedREFW::get("style.css").unwrap().data()).to_vec()
+}
+
+#[actix_web::test]
+async fn serves_a_byte_range() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    let len = style_css().len();
+    assert_eq!(resp.status(), 206);
+    assert_eq!(
+        resp.headers().get("Content-Range").unwrap(),
+        format!("bytes 0-9/{len}").as_str()
+    );
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], &style_css()[0..10]);
+}
+
+#[actix_web::test]
+async fn serves_a_suffix_range() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=-5"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    let data = style_css();
+    assert_eq!(&body[..], &data[data.len() - 5..]);
+}
+
+#[actix_web::test]
+async fn ranges_are_served_uncompressed() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn unsatisfiable_range() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=1000000-"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 416);
+    assert_eq!(
+        resp.headers().get("Content-Range").unwrap(),
+        format!("bytes */{}", style_css().len()).as_str()
+    );
+}
+
+#[actix_web::test]
+async fn other_range_units_are_ignored() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "items=0-10"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("Content-Range").is_none());
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], &style_css()[..]);
+}
+
+#[actix_web::test]
+async fn mismatched_if_range_serves_full_file() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .append_header(("If-Range", "\"not-the-etag\""))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+
+    let etag = EmbedREFW::get("style.css").unwrap().etag();
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .append_header(("If-Range", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 206);
+}
+
+#[actix_web::test]
+async fn advertises_range_support() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.headers().get("Accept-Ranges").unwrap(), "bytes");
+}
+
+#[actix_web::test]
+async fn head_range_request_has_partial_headers_only() {
+    let req = test::TestRequest::default()
+        .method(Method::HEAD)
+        .append_header(("Range", "bytes=0-99"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    let len = style_css().len();
+    assert_eq!(resp.status(), 206);
+    assert_eq!(
+        resp.headers().get("Content-Range").unwrap(),
+        format!("bytes 0-99/{len}").as_str()
+    );
+    assert_eq!(resp.body().size(), BodySize::Sized(100));
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert!(body.is_empty());
+}
+
+fn range_and_gzip_request() -> actix_web::HttpRequest {
+    test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request()
+}
+
+#[actix_web::test]
+async fn prefer_range_serves_uncompressed_range() {
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_range_compression_policy(RangeCompressionPolicy::PreferRange)
+        .respond_to(&range_and_gzip_request());
+    assert_eq!(resp.status(), 206);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], &style_css()[0..10]);
+}
+
+#[actix_web::test]
+async fn prefer_compression_serves_full_compressed_file() {
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_range_compression_policy(RangeCompressionPolicy::PreferCompression)
+        .respond_to(&range_and_gzip_request());
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert!(resp.headers().get("Content-Range").is_none());
+}
+
+#[actix_web::test]
+async fn prefer_compression_keeps_range_for_uncompressed_files() {
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Never)
+        .with_range_compression_policy(RangeCompressionPolicy::PreferCompression)
+        .respond_to(&range_and_gzip_request());
+    assert_eq!(resp.status(), 206);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+}
+
+#[actix_web::test]
+async fn serves_multiple_ranges_as_multipart() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-4, 10-14"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    assert!(resp.headers().get("Content-Range").is_none());
+    let content_type = resp
+        .headers()
+        .get("Content-Type")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/byteranges; boundary=")
+        .unwrap()
+        .to_string();
+
+    let data = style_css();
+    let len = data.len();
+    let mut expected = Vec::new();
+    for (start, end) in [(0, 4), (10, 14)] {
+        expected.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Type: text/css\r\nContent-Range: bytes {start}-{end}/{len}\r\n\r\n"
+            )
+            .as_bytes(),
+        );
+        expected.extend_from_slice(&data[start..=end]);
+        expected.extend_from_slice(b"\r\n");
+    }
+    expected.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], &expected[..]);
+}
+
+#[actix_web::test]
+async fn overlapping_ranges_are_merged() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=5-9, 0-4, 3-7"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    assert_eq!(
+        resp.headers().get("Content-Range").unwrap(),
+        format!("bytes 0-9/{}", style_css().len()).as_str()
+    );
+}
+
+#[actix_web::test]
+async fn too_many_ranges_serve_the_full_file() {
+    let ranges = (0..11)
+        .map(|i| format!("{}-{}", i * 2, i * 2))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let req = test::TestRequest::get()
+        .append_header(("Range", format!("bytes={ranges}")))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], &style_css()[..]);
+
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-0, 2-2, 4-4"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_max_ranges(2)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_max_ranges(3)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+}
+
+#[actix_web::test]
+async fn ranges_can_be_turned_off() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_ranges(false)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Accept-Ranges").unwrap(), "none");
+    assert!(resp.headers().get("Content-Range").is_none());
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], &style_css()[..]);
+}
@@ -0,0 +1,64 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+// The sha256 hash of style.css, base64 encoded.
+const STYLE_DIGEST: &str = "sha-256=:DWZuhaSKt+x6LjHrJK8WnquHSF1jj+4OOnwXnx27JKQ=:";
+
+#[actix_web::test]
+async fn repr_digest_is_sent() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_repr_digest(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Repr-Digest").unwrap(), STYLE_DIGEST);
+
+    let resp = EmbedRE::get("style.css")
+        .into_response()
+        .with_repr_digest(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Repr-Digest").unwrap(), STYLE_DIGEST);
+}
+
+#[actix_web::test]
+async fn repr_digest_is_sent_with_ranges() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=0-9"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_repr_digest(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 206);
+    assert_eq!(resp.headers().get("Repr-Digest").unwrap(), STYLE_DIGEST);
+}
+
+#[actix_web::test]
+async fn repr_digest_is_not_sent_with_compressed_files() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_repr_digest(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert!(resp.headers().get("Repr-Digest").is_none());
+}
+
+#[actix_web::test]
+async fn repr_digest_is_off_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert!(resp.headers().get("Repr-Digest").is_none());
+}
@@ -0,0 +1,48 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn uncompressed_response_is_refused() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_require_compression(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 406);
+
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "identity"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_require_compression(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 406);
+}
+
+#[actix_web::test]
+async fn compressed_response_is_sent() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_require_compression(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+}
+
+#[actix_web::test]
+async fn uncompressed_response_is_sent_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
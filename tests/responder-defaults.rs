@@ -0,0 +1,133 @@
+use actix_http::body::MessageBody;
+use actix_web::test;
+use actix_web::{
+    dev::{ServiceFactory, ServiceRequest, ServiceResponse},
+    route, web, App,
+};
+use actix_web_rust_embed_responder::{
+    Compress, EmbedResponse, EmbedableFileResponse, IntoResponse, ResponderDefaults,
+};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[route("/plain/{path:.*}", method = "GET", method = "HEAD")]
+async fn plain_handler(path: web::Path<String>) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedREFW::get(&path).into_response()
+}
+
+#[route("/override/{path:.*}", method = "GET", method = "HEAD")]
+async fn override_handler(path: web::Path<String>) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedREFW::get(&path)
+        .into_response()
+        .with_cache_control("no-store")
+}
+
+fn make_app(
+    defaults: ResponderDefaults,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Response = ServiceResponse<impl MessageBody>,
+        Config = (),
+        InitError = (),
+        Error = actix_web::Error,
+    >,
+> {
+    App::new()
+        .app_data(defaults)
+        .service(plain_handler)
+        .service(override_handler)
+}
+
+#[actix_web::test]
+async fn defaults_apply_to_responses() {
+    let app = test::init_service(make_app(
+        ResponderDefaults::new()
+            .with_cache_control("public, max-age=3600")
+            .use_compression(Compress::Always),
+    ))
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/plain/style.css")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=3600"
+    );
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+}
+
+#[actix_web::test]
+async fn response_settings_override_defaults() {
+    let app = test::init_service(make_app(
+        ResponderDefaults::new().with_cache_control("public, max-age=3600"),
+    ))
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/override/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+}
+
+#[actix_web::test]
+async fn defaults_can_be_wrapped_in_data() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(
+                ResponderDefaults::new().with_cache_control("public, max-age=60"),
+            ))
+            .service(plain_handler),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/plain/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=60"
+    );
+}
+
+#[actix_web::test]
+async fn no_defaults_keeps_no_cache() {
+    let app = test::init_service(App::new().service(plain_handler)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/plain/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-cache");
+}
+
+#[actix_web::test]
+async fn other_settings_are_not_defaulted() {
+    let app = test::init_service(make_app(
+        ResponderDefaults::new().with_cache_control("public, max-age=3600"),
+    ))
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/plain/style.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.headers().get("Expires").is_none());
+    assert_eq!(resp.headers().get("Accept-Ranges").unwrap(), "bytes");
+
+    // Errors still aren't cached, the default Cache-Control is only for files.
+    let req = test::TestRequest::get()
+        .uri("/plain/missing.css")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+}
@@ -0,0 +1,50 @@
+use actix_web_rust_embed_responder::{EmbedResponse, EmbedableFileResponse, IntoResponse};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[test]
+fn accessors_read_the_wrapped_file() {
+    let file = EmbedREFW::get("style.css").unwrap();
+    let resp: EmbedResponse<EmbedableFileResponse> = EmbedREFW::get("style.css").into_response();
+
+    assert!(resp.file().is_some());
+    assert_eq!(resp.file_name().as_deref(), Some("style.css"));
+    assert_eq!(resp.content_length(), Some(file.data().len()));
+    assert_eq!(
+        resp.etag(),
+        Some(AsRef::<str>::as_ref(&file.etag()).to_string())
+    );
+    assert_eq!(
+        resp.mime_type()
+            .map(|v| AsRef::<str>::as_ref(&v).to_string()),
+        Some("text/css".to_string())
+    );
+}
+
+#[test]
+fn accessors_for_rust_embed() {
+    let file = EmbedRE::get("style.css").unwrap();
+    let resp = file.clone().into_response();
+
+    assert_eq!(resp.file_name(), None);
+    assert_eq!(resp.content_length(), Some(file.data.len()));
+    assert!(resp.etag().unwrap().starts_with('"'));
+}
+
+#[test]
+fn accessors_for_missing_file() {
+    let resp: EmbedResponse<EmbedableFileResponse> =
+        EmbedREFW::get("does-not-exist.txt").into_response();
+
+    assert!(resp.file().is_none());
+    assert_eq!(resp.file_name(), None);
+    assert_eq!(resp.content_length(), None);
+    assert!(resp.etag().is_none());
+}
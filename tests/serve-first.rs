@@ -0,0 +1,47 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{serve_first, EmbedResponse, EmbedableFileResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/localized/"]
+struct EmbedREFW;
+
+async fn body_of(resp: EmbedResponse<EmbedableFileResponse>) -> (u16, String) {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = resp.respond_to(&req);
+    let status = resp.status().as_u16();
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    (status, String::from_utf8_lossy(&body).to_string())
+}
+
+#[actix_web::test]
+async fn first_existing_path_is_served() {
+    let (status, body) = body_of(serve_first(
+        EmbedREFW::get,
+        &["/en/page.html", "/page.html"],
+    ))
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "<p>Hello in English</p>\n");
+}
+
+#[actix_web::test]
+async fn fallback_is_served() {
+    let (status, body) = body_of(serve_first(
+        EmbedREFW::get,
+        &["/en/about.html", "/about.html"],
+    ))
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "<p>About</p>\n");
+}
+
+#[actix_web::test]
+async fn not_found_if_none_exist() {
+    let (status, _) = body_of(serve_first(
+        EmbedREFW::get,
+        &["/en/nope.html", "/nope.html"],
+    ))
+    .await;
+    assert_eq!(status, 404);
+}
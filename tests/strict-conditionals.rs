@@ -0,0 +1,64 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn malformed_range_is_ignored_by_default() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=oops"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn malformed_range_is_rejected_in_strict_mode() {
+    let req = test::TestRequest::get()
+        .append_header(("Range", "bytes=oops"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_strict_conditionals(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn strict_mode_rejects_other_malformed_conditionals() {
+    for (name, value) in [
+        ("If-None-Match", "not-quoted"),
+        ("If-Modified-Since", "yesterday"),
+        ("If-Unmodified-Since", "Wed, 21 Oct 2015 09:28:00 +0200"),
+        ("If-Range", "\"a\", \"b\""),
+        ("If-Range", "Wed, 21 Oct 2015 09:28:00 +0200"),
+    ] {
+        let req = test::TestRequest::get()
+            .append_header((name, value))
+            .append_header(("Range", "bytes=0-10"))
+            .to_http_request();
+        let resp = EmbedREFW::get("style.css")
+            .into_response()
+            .with_strict_conditionals(true)
+            .respond_to(&req);
+        assert_eq!(resp.status(), 400, "{name}: {value}");
+    }
+}
+
+#[actix_web::test]
+async fn strict_mode_accepts_valid_conditionals() {
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", "\"other\", W/\"weak\""))
+        .append_header(("If-Modified-Since", "Wed, 21 Oct 2015 07:28:00 GMT"))
+        .append_header(("If-Range", "Wed, 21 Oct 2015 07:28:00 +0000"))
+        .append_header(("Range", "items=0-10"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_strict_conditionals(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+}
@@ -0,0 +1,159 @@
+use actix_web::{body, test, Responder};
+use actix_web_rust_embed_responder::{EmbedRespondable, EmbedResponse, IntoResponse};
+use flate2::read::GzDecoder;
+use rust_embed_for_web::RustEmbed;
+use std::io::Read;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "tests/assets/bom/"]
+struct EmbedRE;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/bom/"]
+struct EmbedREFW;
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+#[actix_web::test]
+async fn bom_is_stripped() {
+    let req = test::TestRequest::get().to_http_request();
+    let original = EmbedREFW::get("data.json").into_response().respond_to(&req);
+    let resp = EmbedREFW::get("data.json")
+        .into_response()
+        .with_strip_bom(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_ne!(
+        resp.headers().get("ETag").unwrap(),
+        original.headers().get("ETag").unwrap()
+    );
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"{\"hello\": \"world\"}\n");
+
+    let req = test::TestRequest::get()
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("data.json")
+        .into_response()
+        .with_strip_bom(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn bom_is_kept_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("data.json").into_response().respond_to(&req);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert!(body.starts_with(BOM));
+}
+
+#[actix_web::test]
+async fn files_without_bom_are_unchanged() {
+    let req = test::TestRequest::get().to_http_request();
+    let original = EmbedREFW::get("plain.json")
+        .into_response()
+        .respond_to(&req);
+    let resp = EmbedREFW::get("plain.json")
+        .into_response()
+        .with_strip_bom(true)
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("ETag").unwrap(),
+        original.headers().get("ETag").unwrap()
+    );
+}
+
+#[actix_web::test]
+async fn bom_is_stripped_from_rust_embed_files() {
+    // rust-embed files don't know their type, so it comes from the path.
+    let req = test::TestRequest::get().uri("/data.json").to_http_request();
+    let resp = EmbedRE::get("data.json")
+        .into_response()
+        .with_strip_bom(true)
+        .respond_to(&req);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"{\"hello\": \"world\"}\n");
+
+    let req = test::TestRequest::get().uri("/data").to_http_request();
+    let resp = EmbedRE::get("data.json")
+        .into_response()
+        .with_strip_bom(true)
+        .respond_to(&req);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert!(body.starts_with(BOM));
+}
+
+#[actix_web::test]
+async fn content_type_parameters_are_ignored() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedRE::get("data.json")
+        .into_response()
+        .with_content_type("application/json; charset=utf-8")
+        .with_strip_bom(true)
+        .respond_to(&req);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"{\"hello\": \"world\"}\n");
+}
+
+/// A JSON file with a byte order mark and precompressed versions, like
+/// `rust-embed-for-web` files in release builds.
+struct PrecompressedJson;
+
+impl EmbedRespondable for PrecompressedJson {
+    type Data = &'static [u8];
+    type DataGzip = &'static [u8];
+    type DataBr = &'static [u8];
+    type MimeType = &'static str;
+    type ETag = &'static str;
+    type LastModified = &'static str;
+
+    fn data(&self) -> Self::Data {
+        b"\xef\xbb\xbf{}"
+    }
+
+    // The stripped file can't use these, so their contents don't matter.
+    fn data_gzip(&self) -> Option<Self::DataGzip> {
+        Some(b"precompressed")
+    }
+
+    fn data_br(&self) -> Option<Self::DataBr> {
+        None
+    }
+
+    fn last_modified_timestamp(&self) -> Option<i64> {
+        None
+    }
+
+    fn last_modified(&self) -> Option<Self::LastModified> {
+        None
+    }
+
+    fn etag(&self) -> Self::ETag {
+        "\"precompressed-json\""
+    }
+
+    fn mime_type(&self) -> Option<Self::MimeType> {
+        Some("application/json")
+    }
+}
+
+#[actix_web::test]
+async fn stripped_files_are_compressed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedResponse::new(Some(PrecompressedJson))
+        .with_strip_bom(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Encoding");
+
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    let mut decompressed = String::new();
+    GzDecoder::new(&body[..])
+        .read_to_string(&mut decompressed)
+        .unwrap();
+    assert_eq!(decompressed, "{}");
+}
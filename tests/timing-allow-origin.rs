@@ -0,0 +1,28 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn timing_allow_origin_is_sent() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_timing_allow_origin("https://example.com")
+        .respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("Timing-Allow-Origin").unwrap(),
+        "https://example.com"
+    );
+}
+
+#[actix_web::test]
+async fn timing_allow_origin_is_not_sent_by_default() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert!(resp.headers().get("Timing-Allow-Origin").is_none());
+}
@@ -0,0 +1,91 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use actix_web::{
+    body::{BodySize, MessageBody},
+    test, Responder,
+};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+type Fields = Arc<Mutex<HashMap<String, String>>>;
+
+/// Collects the fields of the `embed_response` span.
+#[derive(Default)]
+struct Collector(Fields);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        if span.metadata().name() == "embed_response" {
+            span.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+        }
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, values: &Record<'_>) {
+        values.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[actix_web::test]
+async fn span_records_the_response() {
+    let collector = Collector::default();
+    let fields = collector.0.clone();
+    let req = test::TestRequest::get()
+        .uri("/index.html")
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = tracing::subscriber::with_default(collector, || {
+        EmbedREFW::get("index.html")
+            .into_response()
+            .use_compression(Compress::Always)
+            .respond_to(&req)
+    });
+    assert_eq!(resp.status(), 200);
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(fields["path"], "/index.html");
+    assert_eq!(fields["status"], "200");
+    assert_eq!(fields["encoding"], "gzip");
+    let BodySize::Sized(len) = resp.body().size() else {
+        panic!("the body should have a known size");
+    };
+    assert_eq!(fields["content_length"], len.to_string());
+}
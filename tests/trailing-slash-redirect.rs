@@ -0,0 +1,49 @@
+use actix_web::{body, test, web, App, HttpRequest};
+use actix_web_rust_embed_responder::{EmbedPath, EmbedResponse, EmbedableFileResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/assets/site/"]
+struct Site;
+
+async fn handler(req: HttpRequest) -> EmbedResponse<EmbedableFileResponse> {
+    EmbedPath::new()
+        .with_prefix("/site")
+        .with_trailing_slash_redirect(true)
+        .resolve(&req, Site::get)
+}
+
+async fn get(path: &str) -> actix_web::dev::ServiceResponse {
+    let app = test::init_service(App::new().default_service(web::to(handler))).await;
+    let req = test::TestRequest::get().uri(path).to_request();
+    test::call_service(&app, req).await
+}
+
+#[actix_web::test]
+async fn directory_redirects_to_trailing_slash() {
+    let resp = get("/site/docs").await;
+    assert_eq!(resp.status(), 301);
+    assert_eq!(resp.headers().get("Location").unwrap(), "/site/docs/");
+
+    let resp = get("/site/docs?page=2&q=a").await;
+    assert_eq!(resp.status(), 301);
+    assert_eq!(
+        resp.headers().get("Location").unwrap(),
+        "/site/docs/?page=2&q=a"
+    );
+}
+
+#[actix_web::test]
+async fn trailing_slash_serves_the_index() {
+    let resp = get("/site/docs/").await;
+    assert_eq!(resp.status(), 200);
+    let body = body::to_bytes(resp.into_body()).await.unwrap();
+    assert_eq!(body, include_str!("assets/site/docs/index.html"));
+}
+
+#[actix_web::test]
+async fn files_and_missing_paths_are_not_redirected() {
+    assert_eq!(get("/site/about.html").await.status(), 200);
+    assert_eq!(get("/site/about").await.status(), 404);
+    assert_eq!(get("/site/missing").await.status(), 404);
+}
@@ -0,0 +1,87 @@
+use actix_web::{http::StatusCode, route, test, web, App, HttpResponse, ResponseError};
+use actix_web_rust_embed_responder::{
+    EmbedResponse, EmbedableFileResponse, NotFound, TryIntoResponse,
+};
+use rust_embed_for_web::RustEmbed;
+use std::fmt;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct Embed;
+
+#[route("/direct/{path:.*}", method = "GET", method = "HEAD")]
+async fn direct(
+    path: web::Path<String>,
+) -> Result<EmbedResponse<EmbedableFileResponse>, actix_web::Error> {
+    Ok(Embed::get(&path).try_into_response()?)
+}
+
+#[derive(Debug)]
+enum MyError {
+    Missing,
+}
+
+impl fmt::Display for MyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing")
+    }
+}
+
+impl ResponseError for MyError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::GONE
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::Gone().body("gone")
+    }
+}
+
+impl From<NotFound> for MyError {
+    fn from(_: NotFound) -> Self {
+        MyError::Missing
+    }
+}
+
+#[route("/custom/{path:.*}", method = "GET", method = "HEAD")]
+async fn custom(path: web::Path<String>) -> Result<EmbedResponse<EmbedableFileResponse>, MyError> {
+    let response = Embed::get(&path).try_into_response()?;
+    Ok(response.with_cache_control("max-age=60"))
+}
+
+#[actix_web::test]
+async fn missing_file_is_not_found() {
+    let app = test::init_service(App::new().service(direct).service(custom)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/direct/index.html")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let req = test::TestRequest::get()
+        .uri("/direct/missing.html")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+}
+
+#[actix_web::test]
+async fn not_found_converts_into_custom_errors() {
+    let app = test::init_service(App::new().service(direct).service(custom)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/custom/index.html")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Cache-Control").unwrap(), "max-age=60");
+
+    let req = test::TestRequest::get()
+        .uri("/custom/missing.html")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 410);
+    assert_eq!(test::read_body(resp).await, "gone");
+}
@@ -0,0 +1,69 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn uncompressed_length_is_sent_with_compressed_files() {
+    let len = EmbedREFW::get("style.css").unwrap().data().len();
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_uncompressed_length_header(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(
+        resp.headers().get("X-Uncompressed-Content-Length").unwrap(),
+        len.to_string().as_str()
+    );
+}
+
+#[actix_web::test]
+async fn uncompressed_length_header_name_can_be_changed() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_uncompressed_length_header_name("X-Original-Size")
+        .respond_to(&req);
+    assert!(resp.headers().get("X-Original-Size").is_some());
+    assert!(resp
+        .headers()
+        .get("X-Uncompressed-Content-Length")
+        .is_none());
+}
+
+#[actix_web::test]
+async fn uncompressed_length_is_not_sent_with_uncompressed_files() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_uncompressed_length_header(true)
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert!(resp
+        .headers()
+        .get("X-Uncompressed-Content-Length")
+        .is_none());
+
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert!(resp
+        .headers()
+        .get("X-Uncompressed-Content-Length")
+        .is_none());
+}
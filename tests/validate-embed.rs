@@ -0,0 +1,77 @@
+use actix_web_rust_embed_responder::{
+    validate_embed, EmbedValidationError, EmbedValidator, EmbedableFileResponse, MemoryFile,
+};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedRE;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "tests/assets/unknown/"]
+struct UnknownRE;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[test]
+fn valid_embed_passes() {
+    assert_eq!(validate_embed::<EmbedRE>(), Ok(()));
+}
+
+#[test]
+fn unknown_mime_types_are_reported() {
+    assert_eq!(
+        validate_embed::<UnknownRE>(),
+        Err(vec![EmbedValidationError::UnknownMimeType {
+            path: "blob.unknownext".to_string()
+        }])
+    );
+}
+
+#[test]
+fn listed_files_are_validated() {
+    let result = EmbedValidator::new().validate_files::<EmbedableFileResponse, _, _, _, _>(
+        ["index.html", "style.css", "missing.js"],
+        EmbedREFW::get,
+    );
+    assert_eq!(
+        result,
+        Err(vec![EmbedValidationError::Missing {
+            path: "missing.js".to_string()
+        }])
+    );
+}
+
+#[test]
+fn missing_precompression_is_reported() {
+    // rust-embed never precompresses files.
+    let errors = EmbedValidator::new()
+        .with_precompression_threshold(1024)
+        .validate::<EmbedRE>()
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        vec![EmbedValidationError::MissingPrecompression {
+            path: "index.html".to_string()
+        }]
+    );
+}
+
+#[test]
+fn empty_etags_are_reported() {
+    for etag in ["\"\"", "W/\"\"", "\" \""] {
+        let result = EmbedValidator::new()
+            .validate_files::<MemoryFile, _, _, _, _>(["style.css"], |_| {
+                Some(MemoryFile::new(&b"body {}"[..], etag))
+            });
+        assert_eq!(
+            result,
+            Err(vec![EmbedValidationError::EmptyEtag {
+                path: "style.css".to_string()
+            }]),
+            "{etag:?}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::{Compress, IntoResponse};
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+#[actix_web::test]
+async fn not_modified_keeps_vary() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Encoding");
+    let etag = resp.headers().get("ETag").unwrap().clone();
+
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .append_header(("If-None-Match", etag))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Encoding");
+}
+
+#[actix_web::test]
+async fn uncompressed_responses_vary_if_others_are_compressed() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert!(resp.headers().get("Content-Encoding").is_none());
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Encoding");
+}
+
+#[actix_web::test]
+async fn files_that_are_never_compressed_do_not_vary() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Never)
+        .respond_to(&req);
+    assert!(resp.headers().get("Vary").is_none());
+
+    let resp = EmbedREFW::get("pexels-david-yu-10075042.jpg")
+        .into_response()
+        .use_compression(Compress::Always)
+        .respond_to(&req);
+    assert!(resp.headers().get("Vary").is_none());
+}
+
+#[actix_web::test]
+async fn extra_vary_headers_are_added() {
+    let req = test::TestRequest::get()
+        .append_header(("Accept-Encoding", "gzip"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Always)
+        .with_vary(&["Accept-Language", "accept-encoding"])
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("Vary").unwrap(),
+        "Accept-Encoding, Accept-Language"
+    );
+
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .use_compression(Compress::Never)
+        .with_vary(&["Accept-Language"])
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("Vary").unwrap(), "Accept-Language");
+}
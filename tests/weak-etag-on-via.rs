@@ -0,0 +1,53 @@
+use actix_web::{test, Responder};
+use actix_web_rust_embed_responder::IntoResponse;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/assets/"]
+struct EmbedREFW;
+
+fn etag() -> String {
+    EmbedREFW::get("style.css").unwrap().etag().to_string()
+}
+
+#[actix_web::test]
+async fn etag_is_weak_through_a_proxy() {
+    let req = test::TestRequest::get()
+        .append_header(("Via", "1.1 proxy.example.com"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_auto_weak_etag_on_via(true)
+        .respond_to(&req);
+    assert_eq!(
+        resp.headers().get("ETag").unwrap(),
+        format!("W/{}", etag()).as_str()
+    );
+
+    // The weak ETag can still revalidate the client's copy.
+    let req = test::TestRequest::get()
+        .append_header(("Via", "1.1 proxy.example.com"))
+        .append_header(("If-None-Match", format!("W/{}", etag())))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_auto_weak_etag_on_via(true)
+        .respond_to(&req);
+    assert_eq!(resp.status(), 304);
+}
+
+#[actix_web::test]
+async fn etag_is_strong_without_a_proxy() {
+    let req = test::TestRequest::get().to_http_request();
+    let resp = EmbedREFW::get("style.css")
+        .into_response()
+        .with_auto_weak_etag_on_via(true)
+        .respond_to(&req);
+    assert_eq!(resp.headers().get("ETag").unwrap(), etag().as_str());
+
+    let req = test::TestRequest::get()
+        .append_header(("Via", "1.1 proxy.example.com"))
+        .to_http_request();
+    let resp = EmbedREFW::get("style.css").into_response().respond_to(&req);
+    assert_eq!(resp.headers().get("ETag").unwrap(), etag().as_str());
+}